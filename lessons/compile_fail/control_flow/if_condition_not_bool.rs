@@ -0,0 +1,8 @@
+// Rust will not automatically try to convert non-Boolean types to a Boolean.
+fn main() {
+    let number = 3;
+
+    if number { //~ ERROR E0308
+        println!("number was three");
+    }
+}
@@ -0,0 +1,8 @@
+// The values that could come from each arm of an if must be the same type.
+fn main() {
+    let condition = true;
+
+    let number = if condition { 5 } else { "six" }; //~ ERROR E0308
+
+    println!("The value of number is: {number}");
+}
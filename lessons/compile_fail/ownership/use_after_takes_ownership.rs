@@ -0,0 +1,12 @@
+// After `takes_ownership(s)` the String has moved into the function, so `s` is no longer valid.
+fn main() {
+    let s = String::from("hello");
+
+    takes_ownership(s);
+
+    println!("{s}"); //~ ERROR E0382
+}
+
+fn takes_ownership(some_string: String) {
+    println!("{}", some_string);
+}
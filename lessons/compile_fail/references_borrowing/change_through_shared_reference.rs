@@ -0,0 +1,10 @@
+// References are immutable by default: `&String` doesn't allow `push_str`.
+fn main() {
+    let s = String::from("hello");
+
+    change(&s);
+}
+
+fn change(some_string: &String) {
+    some_string.push_str(", world"); //~ ERROR E0596
+}
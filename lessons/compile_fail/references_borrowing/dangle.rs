@@ -0,0 +1,10 @@
+// Listing 4-6's dangle: a reference to a String that is dropped on return.
+fn main() {
+    let reference_to_nothing = dangle();
+}
+
+fn dangle() -> &String { //~ ERROR E0106
+    let s = String::from("hello");
+
+    &s
+}
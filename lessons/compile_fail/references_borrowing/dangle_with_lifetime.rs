@@ -0,0 +1,10 @@
+// Naming a lifetime doesn't make a reference to a local valid.
+fn main() {
+    let reference_to_nothing = dangle();
+}
+
+fn dangle<'a>() -> &'a String {
+    let s = String::from("hello");
+
+    &s //~ ERROR E0515
+}
@@ -0,0 +1,13 @@
+// The struct can't be used after the text it borrows from is dropped.
+struct ImportantExcerpt<'a> {
+    part: &'a str,
+}
+
+fn main() {
+    let i;
+    {
+        let novel = String::from("Call me Ishmael. Some years ago...");
+        i = ImportantExcerpt { part: &novel[..] }; //~ ERROR E0597
+    }
+    println!("{}", i.part);
+}
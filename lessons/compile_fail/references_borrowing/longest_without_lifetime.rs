@@ -0,0 +1,16 @@
+// The result could borrow from either parameter, so elision can't name its lifetime.
+fn main() {
+    let string1 = String::from("abcd");
+    let string2 = "xyz";
+
+    let result = longest(string1.as_str(), string2);
+    println!("The longest string is {result}");
+}
+
+fn longest(x: &str, y: &str) -> &str { //~ ERROR E0106
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
@@ -0,0 +1,10 @@
+// `&mut s` needs `let mut s`.
+fn main() {
+    let s = String::from("hello");
+
+    change(&mut s); //~ ERROR E0596
+}
+
+fn change(some_string: &mut String) {
+    some_string.push_str(", world");
+}
@@ -0,0 +1,10 @@
+// No mutable reference while immutable ones are still in use.
+fn main() {
+    let mut s = String::from("hello");
+
+    let r1 = &s; // no problem
+    let r2 = &s; // no problem
+    let r3 = &mut s; //~ ERROR E0502
+
+    println!("{}, {}, and {}", r1, r2, r3);
+}
@@ -0,0 +1,10 @@
+// A reference stored in a struct needs a lifetime parameter.
+struct ImportantExcerpt {
+    part: &str, //~ ERROR E0106
+}
+
+fn main() {
+    let novel = String::from("Call me Ishmael. Some years ago...");
+    let i = ImportantExcerpt { part: &novel[..4] };
+    println!("{}", i.part);
+}
@@ -0,0 +1,7 @@
+// Dereferencing a `&` reference gives read-only access.
+fn main() {
+    let x = 5;
+    let r = &x;
+    *r += 1; //~ ERROR E0594
+    println!("{x}");
+}
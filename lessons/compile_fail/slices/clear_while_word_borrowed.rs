@@ -0,0 +1,22 @@
+// A slice borrows the String, so the String can't be cleared while the slice is in use.
+fn main() {
+    let mut s = String::from("hello world");
+
+    let word = first_word(&s);
+
+    s.clear(); //~ ERROR E0502
+
+    println!("the first word is: {word}");
+}
+
+fn first_word(s: &str) -> &str {
+    let bytes = s.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[0..i];
+        }
+    }
+
+    &s[..]
+}
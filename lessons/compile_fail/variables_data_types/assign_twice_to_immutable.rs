@@ -0,0 +1,7 @@
+// Variables are immutable by default: assigning to `x` a second time needs `let mut x`.
+fn main() {
+    let x = 5;
+    println!("The value of x is: {x}");
+    x = 6; //~ ERROR E0384
+    println!("The value of x is: {x}");
+}
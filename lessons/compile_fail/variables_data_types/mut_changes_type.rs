@@ -0,0 +1,6 @@
+// Assigning to a `mut` variable can't change its type; only shadowing can.
+fn main() {
+    let mut spaces = "   ";
+    spaces = spaces.len(); //~ ERROR E0308
+    println!("{spaces}");
+}
@@ -0,0 +1,6 @@
+// `[value; N]` copies the value, so it must be Copy.
+fn main() {
+    let s = String::new();
+    let strings = [s; 3]; //~ ERROR E0277
+    println!("{strings:?}");
+}
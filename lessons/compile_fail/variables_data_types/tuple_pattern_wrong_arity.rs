@@ -0,0 +1,5 @@
+// A tuple pattern needs exactly as many elements as the tuple.
+fn main() {
+    let (x, y) = (500, 6.4, 1); //~ ERROR E0308
+    println!("{x} {y}");
+}
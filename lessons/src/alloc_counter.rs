@@ -0,0 +1,171 @@
+// Counting allocations
+// The ownership lesson claims that `let s2 = s1` never copies heap data and
+// that `clone` is the expensive one. CountingAllocator checks that claim by
+// counting what actually reaches the allocator.
+//
+// It is opt-in: a binary or test that wants numbers installs it itself,
+//
+//     #[global_allocator]
+//     static ALLOCATOR: CountingAllocator = CountingAllocator;
+//
+// and then wraps the code it is interested in with `measure`:
+//
+//     let (s2, stats) = measure(|| s1.clone());
+//     assert_eq!(stats.allocations, 1);
+//
+// Counts are kept per thread, so tests running in parallel don't see each
+// other's allocations.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// What `measure` saw reach the allocator.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Fresh heap blocks handed out.
+    pub allocations: usize,
+    /// Blocks grown or shrunk in place or by moving, e.g. by `push_str`.
+    pub reallocations: usize,
+    /// Blocks given back, e.g. when a String is dropped.
+    pub deallocations: usize,
+    /// Bytes requested by allocations, plus any growth from reallocations.
+    pub bytes_allocated: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, {}, {}, {} requested",
+            plural(self.allocations, "allocation"),
+            plural(self.reallocations, "reallocation"),
+            plural(self.deallocations, "deallocation"),
+            plural(self.bytes_allocated, "byte")
+        )
+    }
+}
+
+fn plural(n: usize, one: &str) -> String {
+    if n == 1 {
+        format!("1 {one}")
+    } else {
+        format!("{n} {one}s")
+    }
+}
+
+/// A global allocator that forwards to `System` and counts calls made
+/// inside `measure`.
+pub struct CountingAllocator;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // `const` initialisers: the allocator must not allocate to reach its counters.
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static STATS: Cell<Stats> = const {
+        Cell::new(Stats { allocations: 0, reallocations: 0, deallocations: 0, bytes_allocated: 0 })
+    };
+}
+
+fn count(update: impl FnOnce(&mut Stats)) {
+    INSTALLED.store(true, Ordering::Relaxed);
+    // `try_with`: the allocator is still called while thread-locals are torn down.
+    let counting = COUNTING.try_with(Cell::get).unwrap_or(false);
+    if counting {
+        let _ = STATS.try_with(|stats| {
+            let mut current = stats.get();
+            update(&mut current);
+            stats.set(current);
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(|stats| {
+            stats.allocations += 1;
+            stats.bytes_allocated += layout.size();
+        });
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count(|stats| {
+            stats.allocations += 1;
+            stats.bytes_allocated += layout.size();
+        });
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(|stats| {
+            stats.reallocations += 1;
+            stats.bytes_allocated += new_size.saturating_sub(layout.size());
+        });
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        count(|stats| stats.deallocations += 1);
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Whether a `CountingAllocator` is the global allocator of this program.
+pub fn is_installed() -> bool {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        // Nothing has been allocated yet (or someone else is allocating);
+        // make one allocation so `count` gets a chance to run.
+        drop(std::hint::black_box(Box::new(0u8)));
+    }
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f` and returns its result along with the allocator calls it made on
+/// this thread.
+///
+/// # Panics
+///
+/// If no `CountingAllocator` is installed: every count would be zero, which
+/// would look like proof that nothing allocates.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Stats) {
+    assert!(
+        is_installed(),
+        "alloc_counter::measure needs `#[global_allocator] static A: CountingAllocator = CountingAllocator;`"
+    );
+    // Save the outer measurement, if any, so `measure` calls can nest.
+    let outer =
+        Outer { counting: COUNTING.with(|c| c.replace(true)), stats: STATS.with(|s| s.replace(Stats::default())) };
+
+    let result = f();
+
+    let stats = STATS.with(Cell::get);
+    drop(outer);
+    (result, stats)
+}
+
+/// The measurement `measure` interrupted. Dropping it, also when `f`
+/// panics, puts it back with this measurement's counts added.
+struct Outer {
+    counting: bool,
+    stats: Stats,
+}
+
+impl Drop for Outer {
+    fn drop(&mut self) {
+        COUNTING.with(|c| c.set(self.counting));
+        let inner = STATS.with(|s| s.replace(self.stats));
+        if self.counting {
+            STATS.with(|s| {
+                let mut outer = s.get();
+                outer.allocations += inner.allocations;
+                outer.reallocations += inner.reallocations;
+                outer.deallocations += inner.deallocations;
+                outer.bytes_allocated += inner.bytes_allocated;
+                s.set(outer);
+            });
+        }
+    }
+}
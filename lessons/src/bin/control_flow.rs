@@ -0,0 +1,640 @@
+use std::io::BufRead as _;
+use std::io::Write as _;
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+use lessons::countdown::{Abort, Countdown, ManualClock, Outcome, SystemClock, Tick};
+use lessons::divisibility::{self, Classifier, Mode, Rule};
+use lessons::examples::{self, Command, Example};
+use lessons::interpreter;
+use lessons::loop_bench::{self, Config};
+use lessons::loop_trace::{Kind, LoopTrace};
+
+// if Expressions
+
+fn if_else() {
+    let number = 3;
+
+    if number < 5 {
+        println!("condition was true");
+    } else {
+        println!("condition was false");
+    }
+}
+
+// Blocks of code associated with the conditions in if expressions are sometimes called arm
+// fn main() {
+//     let number = 3;
+
+//     if number { //Rust will not automatically try to convert non-Boolean types to a Boolean. You must be explicit and always provide if with a Boolean as its condition.
+//         println!("number was three");
+//     }
+// }
+// (doesn't compile: error[E0308], checked by compile_fail/control_flow/if_condition_not_bool.rs)
+
+fn else_if() {
+    let number = 6;
+
+    if number % 4 == 0 {
+        println!("number is divisible by 4");
+    } else if number % 3 == 0 {
+        println!("number is divisible by 3");
+    } else if number % 2 == 0 {
+        println!("number is divisible by 2");
+    } else {
+        println!("number is not divisible by 4, 3, or 2");
+    }
+}
+
+// Using too many else if expressions can clutter your code, so if you have more than one, you might want to refactor your code.
+// describes a powerful Rust branching construct called match for these cases.
+
+// lessons::divisibility turns the chain into data: an ordered list of divisor -> label rules.
+// First-match mode is the else-if chain; all-match mode reports every divisor.
+// The classifier's match on slice patterns gives the same answers as the if chain (tests/divisibility.rs).
+fn else_if_match() {
+    for number in [6, 8, 9, 7] {
+        println!(
+            "{number}: if chain says {:?}, match says {:?}",
+            divisibility::book_if_chain(number),
+            divisibility::book_match(number)
+        );
+    }
+}
+
+fn classify_rules() {
+    let classifier = Classifier::new(divisibility::book_rules(), Mode::FirstMatch).unwrap();
+    print!("{}", classifier.table(1..=12));
+}
+
+fn classify_all() {
+    let classifier = Classifier::new(divisibility::book_rules(), Mode::AllMatches).unwrap();
+    print!("{}", classifier.table(1..=12));
+}
+
+// Rule order matters in first-match mode: 15 has to come before 3 and 5.
+fn classify_fizzbuzz() {
+    let rules = vec![Rule::new(15, "FizzBuzz"), Rule::new(3, "Fizz"), Rule::new(5, "Buzz")];
+    let classifier = Classifier::new(rules, Mode::FirstMatch).unwrap();
+    let line: Vec<String> = classifier
+        .classify_range(1..=15)
+        .map(|c| c.labels.first().map_or_else(|| c.number.to_string(), |label| label.to_string()))
+        .collect();
+    println!("{}", line.join(" "));
+}
+
+// Using if in a let Statement
+// Because if is an expression, we can use it on the right side of a let statement to assign the outcome to a variable
+fn if_in_let() {
+    let condition = true;
+    let number = if condition { 5 } else { 6 };
+
+    println!("The value of number is: {number}");
+}
+// the value of the whole if expression depends on which block of code executes.
+// This means the values that have the potential to be results from each arm of the if must be the same type
+// fn main() {
+//     let condition = true;
+
+//     let number = if condition { 5 } else { "six" };
+
+//     println!("The value of number is: {number}");
+// }
+// (doesn't compile: error[E0308], checked by compile_fail/control_flow/if_else_incompatible.rs)
+
+// Repeating Code with loop
+// The loop keyword tells Rust to execute a block of code over and over again forever or until you explicitly tell it to stop.
+
+// fn main() {
+//     loop {
+//         println!("again!");
+//     }
+// }
+// (Not registered as an example: it never stops on its own.)
+// You can place the break keyword within the loop to tell the program when to stop executing the loop.
+
+// We also used continue in the guessing game, which in a loop tells the program to skip over any remaining code in this iteration of the loop and go to the next iteration.
+
+// Returning Values from Loops
+
+fn loop_result() {
+    let mut counter = 0;
+
+    let result = loop {
+        counter += 1;
+
+        if counter == 20 {
+            break counter * 2;
+        }
+    };
+
+    println!("The result is {result}");
+}
+
+// Traced, the break counter * 2 shows up as the value the loop ends with.
+fn loop_result_trace() {
+    let mut t = LoopTrace::new();
+    let mut counter = 0;
+
+    t.enter(Kind::Loop, None);
+    let result = loop {
+        t.iteration();
+        counter += 1;
+
+        if counter == 20 {
+            t.break_value(None, &(counter * 2));
+            break counter * 2;
+        }
+    };
+
+    t.note(format!("The result is {result}"));
+    print!("{t}");
+}
+
+// Loop Labels to Disambiguate Between Multiple Loops
+// If you have loops within loops, break and continue apply to the innermost loop at that point.
+// You can optionally specify a loop label on a loop that you can then use with break or continue to specify that those keywords apply to the labeled loop instead of the innermost loop.
+// Loop labels must begin with a single quote
+fn loop_labels() {
+    let mut count = 0;
+    'counting_up: loop {
+        println!("count = {count}");
+        let mut remaining = 10;
+
+        loop {
+            println!("remaining = {remaining}");
+            if remaining == 9 {
+                break;
+            }
+            if count == 2 {
+                break 'counting_up;
+            }
+            remaining -= 1;
+        }
+
+        count += 1;
+    }
+    println!("End count = {count}");
+}
+
+// The same loops with a LoopTrace alongside: each iteration, break and break 'counting_up is logged
+// with the depth it happens at, so the timeline shows which loop every break leaves.
+fn loop_labels_trace() {
+    let mut t = LoopTrace::new();
+    let mut count = 0;
+
+    t.enter(Kind::Loop, Some("'counting_up"));
+    'counting_up: loop {
+        t.iteration();
+        t.note(format!("count = {count}"));
+        let mut remaining = 10;
+
+        t.enter(Kind::Loop, None);
+        loop {
+            t.iteration();
+            t.note(format!("remaining = {remaining}"));
+            if remaining == 9 {
+                t.break_loop(None);
+                break;
+            }
+            if count == 2 {
+                t.break_loop(Some("'counting_up"));
+                break 'counting_up;
+            }
+            remaining -= 1;
+        }
+
+        count += 1;
+    }
+    t.note(format!("End count = {count}"));
+    print!("{t}");
+}
+
+// continue works the same way: a bare continue starts the innermost loop's next iteration,
+// continue 'outer abandons the inner loop and moves the outer one on.
+fn loop_continue_trace() {
+    let mut t = LoopTrace::new();
+
+    t.enter(Kind::For, Some("'outer"));
+    'outer: for i in 1..=3 {
+        t.iteration();
+        t.enter(Kind::For, None);
+        for j in 1..=3 {
+            t.iteration();
+            if j == 2 {
+                t.continue_loop(None);
+                continue;
+            }
+            if i == 2 {
+                t.continue_loop(Some("'outer"));
+                continue 'outer;
+            }
+            t.note(format!("i = {i}, j = {j}"));
+        }
+        t.exit();
+    }
+    t.exit();
+    print!("{t}");
+}
+
+// Conditional Loops with while
+fn while_countdown() {
+    let mut number = 3;
+
+    while number != 0 {
+        println!("{number}!");
+
+        number -= 1;
+    }
+
+    println!("LIFTOFF!!!");
+}
+
+// lessons::countdown::Countdown is this loop with the 3, the 1 and the pace made configurable.
+// run_while is the same while number != 0 loop; a hook does the printing, and a ManualClock
+// stands in for the one-second sleeps so the example doesn't wait.
+fn say(tick: &Tick) {
+    println!("{}! (t = {}s)", tick.number, tick.at.as_secs_f64());
+}
+
+fn liftoff(outcome: Outcome, clock: &ManualClock) {
+    println!("{outcome} (t = {}s)", clock.now.as_secs_f64());
+}
+
+fn countdown_while() {
+    let mut clock = ManualClock::new();
+    let mut countdown = Countdown::new(3).delay(Duration::from_secs(1));
+    countdown.on_tick(say);
+
+    let outcome = countdown.run_while(&mut clock);
+    liftoff(outcome, &clock);
+}
+
+// Looping Through a Collection with for
+
+fn while_index() {
+    let a = [10, 20, 30, 40, 50];
+    let mut index = 0;
+
+    while index < 5 {
+        println!("the value is: {}", a[index]);
+
+        index += 1;
+    }
+}
+// this approach is error prone; we could cause the program to panic if the index value or test condition is incorrect.
+// It’s also slow, because the compiler adds runtime code to perform the conditional check of whether the index is within the bounds of the array on every iteration through the loop
+fn for_element() {
+    let a = [10, 20, 30, 40, 50];
+
+    for element in a {
+        println!("the value is: {element}");
+    }
+}
+// lessons::loop_bench puts a number on that claim. The same sum written five ways gives the same answer;
+// `cargo run --release --bin control_flow -- bench` times them over arrays of several sizes.
+// With `while index < a.len()` the optimiser can see the index is in bounds and drops the check.
+// "while < bound" stops at a separate bound, like the lesson's `index < 5`, and hides that it equals the length;
+// in release the compiler still turns its checks into one comparison of the last index before the loop,
+// so it keeps pace with `for` too. Only a debug build checks every `a[index]`.
+fn loop_variants() {
+    let a = [10, 20, 30, 40, 50];
+
+    for variant in loop_bench::VARIANTS {
+        println!("{:<13} sums {a:?} to {}", variant.name, (variant.run)(&a));
+    }
+}
+// we’ve now increased the safety of the code and eliminated the chance of bugs that might result from going beyond the end of the array or not going far enough and missing some items.
+fn for_countdown() {
+    for number in (1..4).rev() {
+        println!("{number}!");
+    }
+    println!("LIFTOFF!!!");
+}
+
+// The same countdown as a for loop over an iterator: Countdown::numbers() is (1..=start).rev().step_by(step).
+fn countdown_for() {
+    let mut clock = ManualClock::new();
+    let mut countdown = Countdown::new(3).delay(Duration::from_secs(1));
+    countdown.on_tick(say);
+
+    let outcome = countdown.run_for(&mut clock);
+    liftoff(outcome, &clock);
+}
+
+// A different start and step, run both ways: the ticks come out the same.
+fn countdown_step() {
+    for name in ["run_while", "run_for"] {
+        let mut ticks = Vec::new();
+        let mut clock = ManualClock::new();
+        let mut countdown = Countdown::new(10).step(3).delay(Duration::from_millis(500));
+        countdown.on_tick(|tick| ticks.push(format!("{}@{}s", tick.number, tick.at.as_secs_f64())));
+
+        let outcome = if name == "run_while" { countdown.run_while(&mut clock) } else { countdown.run_for(&mut clock) };
+        drop(countdown);
+        println!("{name}: {} -> {outcome:?}", ticks.join(" "));
+    }
+}
+
+// An Abort handle stops the countdown before its next tick. Here a second hook trips it at 2;
+// the countdown command trips it from another thread when Enter is pressed.
+fn countdown_abort() {
+    let abort = Abort::new();
+    let mut clock = ManualClock::new();
+    let mut countdown = Countdown::new(5).delay(Duration::from_secs(1)).abort_on(abort.clone());
+    countdown.on_tick(say);
+    countdown.on_tick(move |tick| {
+        if tick.number == 2 {
+            println!("(hold!)");
+            abort.abort();
+        }
+    });
+
+    let outcome = countdown.run_for(&mut clock);
+    liftoff(outcome, &clock);
+}
+
+// Trying the rules out
+// lessons::interpreter type checks and runs a tiny expression language with this lesson's if, loop,
+// while, for and blocks, and rejects what rustc rejects with the same error codes. Run your own with
+//     cargo run --bin control_flow -- eval my_program.rs
+const INTERPRETER_PROGRAMS: &[(&str, &str)] = &[
+    (
+        "if in a let statement",
+        "let condition = true;
+let number = if condition { 5 } else { 6 };
+number",
+    ),
+    (
+        "arms of different types",
+        "let condition = true;
+let number = if condition { 5 } else { \"six\" };
+number",
+    ),
+    (
+        "a condition that isn't a bool",
+        "let number = 3;
+if number {
+    print(\"number was three\");
+}",
+    ),
+    (
+        "a block as an expression",
+        "let y = {
+    let x = 3;
+    x + 1
+};
+y",
+    ),
+    (
+        "returning a value from loop",
+        "let mut counter = 0;
+let result = loop {
+    counter += 1;
+    if counter == 10 {
+        break counter * 2;
+    }
+};
+result",
+    ),
+    (
+        "break with a value from while",
+        "let mut number = 3;
+let last = while number != 0 {
+    number -= 1;
+    break number;
+};
+last",
+    ),
+    (
+        "labels and for",
+        "let mut total = 0;
+'rows: for row in 1..=3 {
+    for column in (1..4).rev() {
+        if column == row {
+            continue 'rows;
+        }
+        total += row * column;
+    }
+}
+print(\"total = \", total);
+total",
+    ),
+];
+
+fn interpreter_demo() {
+    for (i, (title, program)) in INTERPRETER_PROGRAMS.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("== {title} ==");
+        println!("{program}");
+        println!();
+        print!("{}", interpreter::report(program));
+    }
+}
+
+const EXAMPLES: &[Example] = &[
+    Example::new("if-else", "if/else on a bool condition", if_else),
+    Example::new("else-if", "if number % 4 / % 3 / % 2 else-if chain", else_if),
+    Example::new("else-if-match", "the else-if chain and the same thing as a match", else_if_match),
+    Example::new("classify-rules", "divisor -> label rules, first match wins, over 1..=12", classify_rules),
+    Example::new("classify-all", "the same rules, every matching label", classify_all),
+    Example::new("classify-fizzbuzz", "FizzBuzz as an ordered rule list", classify_fizzbuzz),
+    Example::new("if-in-let", "let number = if condition { 5 } else { 6 }", if_in_let),
+    Example::new("loop-result", "break counter * 2 returns a value from loop", loop_result),
+    Example::new("loop-result-trace", "loop-result with each iteration and the break value traced", loop_result_trace),
+    Example::new("loop-labels", "'counting_up: break out of the outer loop", loop_labels),
+    Example::new("loop-labels-trace", "loop-labels as a timeline of iterations and breaks by depth", loop_labels_trace),
+    Example::new(
+        "loop-continue-trace",
+        "continue and continue 'outer in nested for loops, traced",
+        loop_continue_trace,
+    ),
+    Example::new("while-countdown", "while number != 0 countdown to LIFTOFF", while_countdown),
+    Example::new("countdown-while", "Countdown::run_while with a tick hook and a manual clock", countdown_while),
+    Example::new("while-index", "while index < 5 walk over an array", while_index),
+    Example::new(
+        "loop-variants",
+        "while index, while < bound, for, iter and get_unchecked sum the same array",
+        loop_variants,
+    ),
+    Example::new("for-element", "for element in a walk over an array", for_element),
+    Example::new("for-countdown", "for number in (1..4).rev() countdown", for_countdown),
+    Example::new("countdown-for", "Countdown::run_for over (1..=start).rev().step_by(step)", countdown_for),
+    Example::new("countdown-step", "start 10, step 3: run_while and run_for tick the same", countdown_step),
+    Example::new("countdown-abort", "an Abort handle tripped by a tick hook stops the countdown", countdown_abort),
+    Example::new(
+        "interpreter-demo",
+        "if, loop and blocks as expressions in a tiny type-checked language",
+        interpreter_demo,
+    ),
+];
+
+fn countdown_command(args: &[String]) -> ExitCode {
+    const USAGE: &str = "usage: control_flow countdown [START] [STEP] [DELAY_MS]   (defaults: 3 1 1000)";
+    if args.len() > 3 {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    }
+    let mut numbers = [3, 1, 1000];
+    for (slot, arg) in numbers.iter_mut().zip(args) {
+        match arg.parse::<u32>() {
+            Ok(n) => *slot = n,
+            Err(e) => {
+                eprintln!("countdown: `{arg}`: {e}\n{USAGE}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+    let [start, step, delay_ms] = numbers;
+    if step == 0 {
+        eprintln!("countdown: a step of 0 never reaches liftoff");
+        return ExitCode::from(2);
+    }
+
+    // Enter aborts. The reader thread is left blocked on stdin when the countdown finishes;
+    // it goes away with the process.
+    let abort = Abort::new();
+    let trip = abort.clone();
+    thread::spawn(move || {
+        let mut line = String::new();
+        if std::io::stdin().lock().read_line(&mut line).is_ok_and(|n| n > 0) {
+            trip.abort();
+        }
+    });
+
+    println!("(press Enter to abort)");
+    let mut countdown = Countdown::new(start).step(step).delay(Duration::from_millis(delay_ms.into())).abort_on(abort);
+    countdown.on_tick(|tick| println!("{}!", tick.number));
+    let outcome = countdown.run_while(&mut SystemClock::new());
+    println!("{outcome}");
+    if outcome == Outcome::Liftoff {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn bench_command(args: &[String]) -> ExitCode {
+    let mut config = Config::default();
+    if !args.is_empty() {
+        let sizes: Result<Vec<usize>, _> = args.iter().map(|arg| arg.parse::<usize>()).collect();
+        match sizes {
+            Ok(sizes) => config.sizes = sizes,
+            Err(e) => {
+                eprintln!("bench: {e}\nusage: control_flow bench [SIZE...]");
+                return ExitCode::from(2);
+            }
+        }
+    }
+    print!("{}", loop_bench::run(&config));
+    ExitCode::SUCCESS
+}
+
+fn classify_command(args: &[String]) -> ExitCode {
+    const USAGE: &str =
+        "usage: control_flow classify [--all] NUMBER|START..=END [DIVISOR[:LABEL]...]   (default rules: 4 3 2)";
+    let (mode, args) = match args {
+        [flag, rest @ ..] if flag == "--all" => (Mode::AllMatches, rest),
+        _ => (Mode::FirstMatch, args),
+    };
+    let Some((target, rule_args)) = args.split_first() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let parse = |text: &str| text.trim().parse::<u64>().map_err(|e| format!("`{text}`: {e}"));
+    let range = match target.split_once("..=") {
+        Some((start, end)) => parse(start).and_then(|start| Ok(start..=parse(end)?)),
+        None => parse(target).map(|n| n..=n),
+    };
+    let range = match range {
+        Ok(range) => range,
+        Err(e) => {
+            eprintln!("classify: {e}\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let rules = if rule_args.is_empty() {
+        Ok(divisibility::book_rules())
+    } else {
+        rule_args.iter().map(|arg| arg.parse::<Rule>()).collect()
+    };
+    match rules.and_then(|rules| Classifier::new(rules, mode)) {
+        Ok(classifier) => {
+            // Rows are written as they're classified: a range can be far too long to hold.
+            let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+            match classifier.write_table(range, &mut out).and_then(|()| out.flush()) {
+                Ok(()) => ExitCode::SUCCESS,
+                // Whoever reads the table stopped early, e.g. `| head`.
+                Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("classify: {e}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("classify: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn eval_command(args: &[String]) -> ExitCode {
+    let [path] = args else {
+        eprintln!("usage: control_flow eval FILE   (use - for stdin)");
+        return ExitCode::from(2);
+    };
+    let source = if path == "-" { std::io::read_to_string(std::io::stdin()) } else { std::fs::read_to_string(path) };
+    let source = match source {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("eval: {path}: {e}");
+            return ExitCode::from(2);
+        }
+    };
+    // What the program printed goes to stdout even if it panics later; errors go to stderr.
+    let mut output = Vec::new();
+    let result = interpreter::run_with_output(&source, &mut output);
+    for line in &output {
+        println!("{line}");
+    }
+    match result {
+        Ok(outcome) => {
+            println!("result: {} = {}", outcome.ty, outcome.value.literal());
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprint!("{}", error.render(&source));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+const COMMANDS: &[Command] = &[
+    Command::new(
+        "bench",
+        "[SIZE...]",
+        "time the while-index, for, iter and get_unchecked sums over arrays of each SIZE",
+        bench_command,
+    ),
+    Command::new(
+        "classify",
+        "[--all] NUMBER|START..=END [DIVISOR[:LABEL]...]",
+        "label numbers by the first (or, with --all, every) rule whose divisor divides them",
+        classify_command,
+    ),
+    Command::new(
+        "countdown",
+        "[START] [STEP] [DELAY_MS]",
+        "a real countdown with a delay per tick; Enter aborts it",
+        countdown_command,
+    ),
+    Command::new("eval", "FILE", "type check and run a program in the tiny expression language", eval_command),
+];
+
+fn main() -> ExitCode {
+    examples::run_with_commands("control_flow", EXAMPLES, COMMANDS)
+}
@@ -1,19 +1,25 @@
+use std::process::ExitCode;
+
+use lessons::examples::{self, Example};
+use lessons::functions::{another_function, five, plus_one};
+
 // main function, which is the entry point of many programs.
 
-// fn main() {
-//     println!("Hello, world!");
+fn parameters() {
+    println!("Hello, world!");
 
-//     another_function(5 , 'h');
-// }
+    another_function(5, 'h');
+}
 
 // fn another_function(x: i32, unit_label: char) {
 //     println!("Another function.value is: {x}{unit_label}");
 // }
+// (another_function lives in src/functions.rs)
 
 // Rust doesn’t care where you define your functions, only that they’re defined somewhere in a scope that can be seen by the caller
-// In function signatures, you must declare the type of each parameter. 
-// This is a deliberate decision in Rust’s design: 
-// requiring type annotations in function definitions means the compiler almost never needs you to use them elsewhere in the code to figure out what type you mean. 
+// In function signatures, you must declare the type of each parameter.
+// This is a deliberate decision in Rust’s design:
+// requiring type annotations in function definitions means the compiler almost never needs you to use them elsewhere in the code to figure out what type you mean.
 // The compiler is also able to give more helpful error messages if it knows what types the function expects.
 
 // Statements are instructions that perform some action and do not return a value.
@@ -21,26 +27,27 @@
 
 // Expressions can be part of statements
 // Calling a function is an expression. Calling a macro is an expression. A new scope block created with curly brackets is an expression
-// fn main() {
-//     let y = {
-//         let x = 3;
-//         x + 1
-//     };
+fn block_expression() {
+    let y = {
+        let x = 3;
+        x + 1
+    };
 
-//     println!("The value of y is: {y}");
-// }
-// Expressions do not include ending semicolons. 
-// If you add a semicolon to the end of an expression, you turn it into a statement, and it will then not return a value. 
+    println!("The value of y is: {y}");
+}
+// Expressions do not include ending semicolons.
+// If you add a semicolon to the end of an expression, you turn it into a statement, and it will then not return a value.
 // Functions with Return Values
-// Functions can return values to the code that calls them. We don’t name return values, but we must declare their type after an arrow (->). 
+// Functions can return values to the code that calls them. We don’t name return values, but we must declare their type after an arrow (->).
 // In Rust, the return value of the function is synonymous with the value of the final expression in the block of the body of a function.
 // You can return early from a function by using the return keyword and specifying a value, but most functions return the last expression implicitly.
 
-fn five() -> i32 {
-    5
-}
+// fn five() -> i32 {
+//     5
+// }
+// (five lives in src/functions.rs)
 
-fn main() {
+fn return_value() {
     let x = five();
 
     println!("The value of x is: {x}");
@@ -48,19 +55,24 @@ fn main() {
 // There are no function calls, macros, or even let statements in the five function—just the number 5 by itself.That’s a perfectly valid function in Rust
 // Second, the five function has no parameters and defines the type of the return value, but the body of the function is a lonely 5 with no semicolon because it’s an expression whose value we want to retur
 
-fn main() {
+fn parameter_and_return() {
     let x = plus_one(5);
 
     println!("The value of x is: {x}");
 }
 
-fn plus_one(x: i32) -> i32 {
-    x + 1
-}
-
-
-
-
-
+// fn plus_one(x: i32) -> i32 {
+//     x + 1
+// }
+// (plus_one lives in src/functions.rs)
 
+const EXAMPLES: &[Example] = &[
+    Example::new("parameters", "another_function(5, 'h') with two typed parameters", parameters),
+    Example::new("block-expression", "let y = { let x = 3; x + 1 }", block_expression),
+    Example::new("five", "five() returns its last expression", return_value),
+    Example::new("plus-one", "plus_one(5) takes a parameter and returns a value", parameter_and_return),
+];
 
+fn main() -> ExitCode {
+    examples::run("functions", EXAMPLES)
+}
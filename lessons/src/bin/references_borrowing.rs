@@ -0,0 +1,312 @@
+// A reference is like a pointer in that it’s an address we can follow to access the data stored at that address; that data is owned by some other variable.
+// Unlike a pointer, a reference is guaranteed to point to a valid value of a particular type for the life of that reference.
+// Here is how you would define and use a calculate_length function that has a reference to an object as a parameter instead of taking ownership of the value.
+
+use std::process::ExitCode;
+
+use lessons::borrowck;
+use lessons::borrowing::{self, calculate_length, calculate_length_as_ref, change, replace}; // see src/borrowing.rs
+use lessons::examples::{self, Command, Example};
+use lessons::lifetimes::{self, first_of, longest, no_dangle, ImportantExcerpt, Pair}; // see src/lifetimes.rs
+use lessons::ownership;
+use lessons::slices::first_word;
+
+fn borrow_length() {
+    let s1 = String::from("hello");
+
+    let len = calculate_length(&s1);
+
+    println!("The length of '{}' is {}.", s1, len);
+}
+// First, notice that all the tuple code in the variable declaration and the function return value is gone.
+// Second, note that we pass &s1 into calculate_length and, in its definition, we take &String rather than String.
+// These ampersands represent references, and they allow you to refer to some value without taking ownership of it.
+// . Because it does not own it, the value it points to will not be dropped when the reference stops being used
+// The opposite of referencing by using & is dereferencing, which is accomplished with the dereference operator, *.
+
+// Side by side: Listing 4-5 (ownership::calculate_length) against the borrowing version.
+fn compare_calculate_length() {
+    let s1 = String::from("hello");
+    let (s2, len) = ownership::calculate_length(s1); // s1 is moved in and has to come back as s2
+    println!("ownership::calculate_length(s1)  -> ({s2:?}, {len}); s1 is gone, we use s2");
+
+    let len = borrowing::calculate_length(&s2); // s2 is only borrowed
+    println!("borrowing::calculate_length(&s2) -> {len}; s2 is still ours: {s2:?}");
+
+    // &String is more specific than it needs to be; AsRef<str> takes literals too.
+    let literal = calculate_length_as_ref("hello");
+    let borrowed = calculate_length_as_ref(&s2);
+    println!("calculate_length_as_ref(\"hello\") -> {literal}, calculate_length_as_ref(&s2) -> {borrowed}");
+}
+
+// Mutable References
+// Just as variables are immutable by default, so are references. We’re not allowed to modify something we have a reference to.
+// fn change(some_string: &String) {
+//     some_string.push_str(", world");
+// }
+// (doesn't compile: error[E0596], checked by compile_fail/references_borrowing/change_through_shared_reference.rs)
+
+// To modify a borrowed value, the variable must be `mut`, the reference `&mut`, and the function must accept `&mut String`.
+fn mutable_reference() {
+    let mut s = String::from("hello");
+
+    change(&mut s);
+
+    println!("{s}");
+}
+// (change lives in src/borrowing.rs)
+
+// Mutable references have one big restriction: if you have a mutable reference to a value, you can have no other references to that value.
+// let mut s = String::from("hello");
+// let r1 = &mut s;
+// let r2 = &mut s;
+// println!("{}, {}", r1, r2);
+// (doesn't compile: error[E0499], checked by compile_fail/references_borrowing/two_mutable_borrows.rs)
+// The benefit of having this restriction is that Rust can prevent data races at compile time. A data race happens when
+//     two or more pointers access the same data at the same time,
+//     at least one of the pointers is being used to write to the data, and
+//     there’s no mechanism being used to synchronize access to the data.
+
+// As always, we can use curly brackets to create a new scope, allowing for multiple mutable references, just not simultaneous ones
+fn one_writer_at_a_time() {
+    let mut s = String::from("hello");
+
+    {
+        let r1 = &mut s;
+        r1.push_str(" from r1");
+    } // r1 goes out of scope here, so we can make a new reference with no problems.
+
+    let r2 = &mut s;
+    r2.push_str(" and r2");
+
+    println!("{s}");
+}
+
+// Rust enforces a similar rule for combining mutable and immutable references.
+// let mut s = String::from("hello");
+// let r1 = &s; // no problem
+// let r2 = &s; // no problem
+// let r3 = &mut s; // BIG PROBLEM
+// println!("{}, {}, and {}", r1, r2, r3);
+// (doesn't compile: error[E0502], checked by compile_fail/references_borrowing/mutable_while_shared.rs)
+// Users of an immutable reference don’t expect the value to suddenly change out from under them!
+// Multiple immutable references are allowed because no one who is just reading the data can affect anyone else’s reading of the data.
+
+// A reference’s scope starts from where it is introduced and continues through the last time that reference is used (non-lexical lifetimes).
+fn non_lexical_lifetimes() {
+    let mut s = String::from("hello");
+
+    let r1 = &s; // no problem
+    let r2 = &s; // no problem
+    println!("{} and {}", r1, r2);
+    // variables r1 and r2 will not be used after this point
+
+    let r3 = &mut s; // no problem
+    r3.push_str(", world");
+    println!("{}", r3);
+}
+
+// Dereferencing through &mut: *r is the value r points at, so *r = ... writes to the original.
+fn deref_mut() {
+    let mut x = 5;
+    let r = &mut x;
+    *r += 1;
+    println!("x = {x}");
+
+    let mut s = String::from("hello");
+    replace(&mut s, "goodbye");
+    println!("s = {s}");
+}
+// (replace lives in src/borrowing.rs: `*some_string = String::from(with)`)
+
+// Dangling References
+// In languages with pointers, it’s easy to erroneously create a dangling pointer—a pointer that references a location in memory that may have been given to someone else—by freeing some memory while preserving a pointer to that memory.
+// In Rust, by contrast, the compiler guarantees that references will never be dangling references.
+// fn dangle() -> &String { // dangle returns a reference to a String
+//     let s = String::from("hello"); // s is a new String
+//     &s // we return a reference to the String, s
+// } // Here, s goes out of scope, and is dropped. Its memory goes away.
+//   // Danger!
+// (doesn't compile: error[E0106], checked by compile_fail/references_borrowing/dangle.rs)
+// this function's return type contains a borrowed value, but there is no value for it to be borrowed from
+// The solution here is to return the String directly:
+fn no_dangle_example() {
+    let s = no_dangle();
+    println!("no_dangle() -> {s:?}; ownership is moved out, and nothing is deallocated");
+}
+// (no_dangle lives in src/lifetimes.rs)
+
+// Looking ahead to lifetimes: when a function returns a reference, the compiler has to know which input it borrows from.
+// fn longest(x: &str, y: &str) -> &str
+// (doesn't compile: error[E0106], checked by compile_fail/references_borrowing/longest_without_lifetime.rs)
+// longest<'a> says the result lives as long as the shorter of the two inputs.
+fn longest_example() {
+    let string1 = String::from("long string is long");
+
+    {
+        let string2 = String::from("xyz");
+        let result = longest(string1.as_str(), string2.as_str());
+        println!("The longest string is {result}");
+    }
+
+    // Moving the use of result past string2's scope doesn't compile:
+    // (error[E0597], checked by compile_fail/references_borrowing/longest_outlives_input.rs)
+    // first_of only ties the result to x, so it may outlive y.
+    let result;
+    {
+        let string2 = String::from("xyz");
+        result = first_of(string1.as_str(), string2.as_str());
+    }
+    println!("first_of outlives string2: {result}");
+}
+
+// Structs can hold references too, with a lifetime parameter on the struct.
+// struct ImportantExcerpt { part: &str }
+// (doesn't compile: error[E0106], checked by compile_fail/references_borrowing/struct_without_lifetime.rs)
+fn struct_with_reference() {
+    let novel = String::from("Call me Ishmael. Some years ago...");
+    let i = ImportantExcerpt::first_sentence(&novel);
+    println!("{i:?}, level {}", i.level());
+    let part = i.announce_and_return_part("here it is");
+    println!("part = {part:?}");
+
+    let pair = Pair { left: first_word(&novel), right: first_word("years ago") };
+    println!("{pair:?}");
+}
+// An excerpt used after novel is dropped doesn't compile:
+// (error[E0597], checked by compile_fail/references_borrowing/excerpt_outlives_text.rs)
+
+fn lifetime_rules() {
+    print!("{}", lifetimes::explain());
+}
+
+// Rules of references
+// At any given time, you can have either one mutable reference or any number of immutable references.
+// References must always be valid.
+
+// Trying the rules out
+// lessons::borrowck checks programs in a tiny Rust-like language against the move and borrow
+// rules above and explains violations the way rustc does. Run your own with
+//     cargo run --bin references_borrowing -- borrowck my_program.rs
+const BORROWCK_PROGRAMS: &[(&str, &str)] = &[
+    (
+        "use after move",
+        "fn main() {
+    let s1 = String::from(\"hello\");
+    let s2 = s1;
+    print(s1);
+}",
+    ),
+    (
+        "two mutable borrows at once",
+        "fn main() {
+    let mut s = String::from(\"hello\");
+    let r1 = &mut s;
+    let r2 = &mut s;
+    print(r1, r2);
+}",
+    ),
+    (
+        "borrows end at their last use",
+        "fn main() {
+    let mut s = String::from(\"hello\");
+    let r1 = &s;
+    let r2 = &s;
+    print(r1, r2);
+    let r3 = &mut s;
+    change(r3);
+    print(r3);
+}
+
+fn change(some_string: &mut String) {
+    *some_string = String::from(\"hello, world\");
+}",
+    ),
+    (
+        "dangling reference",
+        "fn main() {
+    let reference_to_nothing = dangle();
+}
+
+fn dangle() -> &String {
+    let s = String::from(\"hello\");
+    &s
+}",
+    ),
+    (
+        "reference outlives its value",
+        "fn main() {
+    let r;
+    {
+        let x = 5;
+        r = &x;
+    }
+    print(r);
+}",
+    ),
+];
+
+fn borrowck_demo() {
+    for (i, (title, program)) in BORROWCK_PROGRAMS.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("== {title} ==");
+        println!("{program}");
+        println!();
+        match borrowck::report(program) {
+            Some(errors) => print!("{errors}"),
+            None => println!("ok: no borrow errors"),
+        }
+    }
+}
+
+fn borrowck_command(args: &[String]) -> ExitCode {
+    let [path] = args else {
+        eprintln!("usage: references_borrowing borrowck FILE   (use - for stdin)");
+        return ExitCode::from(2);
+    };
+    let source = if path == "-" { std::io::read_to_string(std::io::stdin()) } else { std::fs::read_to_string(path) };
+    let source = match source {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("borrowck: {path}: {e}");
+            return ExitCode::from(2);
+        }
+    };
+    match borrowck::report(&source) {
+        Some(errors) => {
+            print!("{errors}");
+            ExitCode::FAILURE
+        }
+        None => {
+            println!("ok: no borrow errors");
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+const EXAMPLES: &[Example] = &[
+    Example::new("calculate-length", "calculate_length(&s1) borrows instead of moving", borrow_length),
+    Example::new(
+        "compare-calculate-length",
+        "tuple-returning vs borrowing vs AsRef<str> calculate_length",
+        compare_calculate_length,
+    ),
+    Example::new("mutable-reference", "change(&mut s) appends through a mutable reference", mutable_reference),
+    Example::new("one-writer", "two &mut borrows in turn, never at the same time", one_writer_at_a_time),
+    Example::new("non-lexical-lifetimes", "shared borrows end at last use, then &mut is fine", non_lexical_lifetimes),
+    Example::new("deref-mut", "*r += 1 and *s = ... write through &mut", deref_mut),
+    Example::new("no-dangle", "return the String instead of a dangling &String", no_dangle_example),
+    Example::new("longest", "longest<'a> ties its result to both inputs", longest_example),
+    Example::new("struct-with-reference", "ImportantExcerpt<'a> holds a &'a str", struct_with_reference),
+    Example::new("lifetime-rules", "each dangling/lifetime case and the rule it breaks", lifetime_rules),
+    Example::new("borrowck-demo", "the borrow-checker simulator on five small programs", borrowck_demo),
+];
+
+const COMMANDS: &[Command] =
+    &[Command::new("borrowck", "FILE", "check a toy-language program for move and borrow errors", borrowck_command)];
+
+fn main() -> ExitCode {
+    examples::run_with_commands("references_borrowing", EXAMPLES, COMMANDS)
+}
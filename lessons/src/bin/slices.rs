@@ -0,0 +1,137 @@
+// Slices let you reference a contiguous sequence of elements in a collection rather than the whole collection.
+// A slice is a kind of reference, so it does not have ownership.
+
+use std::process::ExitCode;
+
+use lessons::examples::{self, Example};
+use lessons::slices::{byte_range, first_word, first_word_end, nth_word, words}; // see src/slices.rs
+
+// Here’s a small programming problem: write a function that takes a string of words separated by spaces
+// and returns the first word it finds in that string. If the function doesn’t find a space in the string,
+// the whole string must be one word, so the entire string should be returned.
+// Without slices, all we can return is the index of the end of the word (Listing 4-7).
+fn first_word_index() {
+    let mut s = String::from("hello world");
+
+    let word = first_word_end(&s); // word will get the value 5
+    println!("first_word_end(\"{s}\") = {word}");
+
+    s.clear(); // this empties the String, making it equal to ""
+
+    // word still has the value 5 here, but there's no more string that
+    // we could meaningfully use the value 5 with. word is now totally invalid!
+    println!("after s.clear(): s = {s:?}, word = {word}, s.get(..word) = {:?}", s.get(..word));
+}
+// Having to worry about the index in word getting out of sync with the data in s is tedious and error prone!
+
+// String Slices
+// A string slice is a reference to part of a String. Rather than a reference to the entire String, hello is a reference to a portion of it.
+// We create slices using a range within brackets by specifying [starting_index..ending_index],
+// where starting_index is the first position in the slice and ending_index is one more than the last position in the slice.
+fn string_slices() {
+    let s = String::from("hello world");
+
+    let hello = &s[0..5];
+    let world = &s[6..11];
+    println!("&s[0..5] = {hello:?}, &s[6..11] = {world:?}");
+
+    // With Rust’s .. range syntax, you can drop the starting index, the ending index, or both.
+    let len = s.len();
+    println!("&s[..2] = {:?}, &s[3..len] = {:?}, &s[3..] = {:?}, &s[..] = {:?}", &s[..2], &s[3..len], &s[3..], &s[..]);
+
+    // Internally, the slice stores the starting position and the length of the slice.
+    println!(
+        "world is {} bytes starting {} bytes into s's buffer",
+        world.len(),
+        world.as_ptr() as usize - s.as_ptr() as usize
+    );
+}
+
+// With all this information in mind, let’s rewrite first_word to return a slice (Listing 4-9).
+// The signature takes &str instead of &String, so it works on String values and on string literals.
+#[allow(clippy::redundant_slicing)] // Listing 4-9 spells out every form on purpose
+fn first_word_slice() {
+    let my_string = String::from("hello world");
+
+    // `first_word` works on slices of `String`s, whether partial or whole
+    let word = first_word(&my_string[0..6]);
+    println!("first_word(&my_string[0..6]) = {word:?}");
+    let word = first_word(&my_string[..]);
+    println!("first_word(&my_string[..]) = {word:?}");
+    // `first_word` also works on references to `String`s, which are equivalent
+    // to whole slices of `String`s
+    let word = first_word(&my_string);
+    println!("first_word(&my_string) = {word:?}");
+
+    let my_string_literal = "hello world";
+
+    // `first_word` works on slices of string literals, whether partial or whole
+    let word = first_word(&my_string_literal[0..6]);
+    println!("first_word(&my_string_literal[0..6]) = {word:?}");
+    let word = first_word(&my_string_literal[..]);
+    println!("first_word(&my_string_literal[..]) = {word:?}");
+
+    // Because string literals *are* string slices already,
+    // this works too, without the slice syntax!
+    let word = first_word(my_string_literal);
+    println!("first_word(my_string_literal) = {word:?}");
+}
+
+// Now the compiler keeps the slice and the String in step:
+// let mut s = String::from("hello world");
+// let word = first_word(&s);
+// s.clear(); // error!
+// println!("the first word is: {word}");
+// (doesn't compile: error[E0502], checked by compile_fail/slices/clear_while_word_borrowed.rs)
+// clear needs to truncate the String, so it needs a mutable reference, while word is still an immutable one.
+
+// The same idea goes further than the first word: nth_word and words hand out slices of the original string too.
+fn nth_word_and_words() {
+    let s = String::from("  the quick\tbrown   fox ");
+
+    for n in 0..5 {
+        println!("nth_word(s, {n}) = {:?}", nth_word(&s, n));
+    }
+    for word in words(&s) {
+        println!("{word:?} is s[{:?}]", byte_range(&s, word).unwrap());
+    }
+}
+
+// String slice range indices must occur at valid UTF-8 character boundaries.
+// 'ℤ' takes 3 bytes and '😻' takes 4, so byte 1 is in the middle of a character.
+fn utf8_boundaries() {
+    let s = String::from("ℤ😻 are heart-eyed");
+
+    println!("s.len() = {} bytes, s.chars().count() = {} chars", s.len(), s.chars().count());
+    for i in 0..=s.find(' ').unwrap() {
+        println!("  byte {i}: is_char_boundary = {}, s.get(..{i}) = {:?}", s.is_char_boundary(i), s.get(..i));
+    }
+    // &s[..1] would panic: "byte index 1 is not a char boundary".
+    println!("first_word(&s) = {:?}", first_word(&s));
+    println!("nth_word(&s, 2) = {:?}", nth_word(&s, 2));
+    println!("first_word(\"ℤ\\u{{3000}}😻\") = {:?} (U+3000 is an ideographic space)", first_word("ℤ\u{3000}😻"));
+}
+
+// Other Slices
+// String slices, as you might imagine, are specific to strings. But there’s a more general slice type too.
+fn other_slices() {
+    let a = [1, 2, 3, 4, 5];
+
+    let slice = &a[1..3];
+
+    assert_eq!(slice, &[2, 3]);
+    println!("&a[1..3] = {slice:?}");
+}
+
+const EXAMPLES: &[Example] = &[
+    Example::new("first-word-index", "Listing 4-7: an index goes stale after s.clear()", first_word_index),
+    Example::new("string-slices", "&s[0..5], &s[..2], &s[3..] and &s[..]", string_slices),
+    Example::new("first-word", "Listing 4-9: first_word(&str) on Strings and literals", first_word_slice),
+    Example::new("words", "nth_word and words return slices of the original", nth_word_and_words),
+    Example::new("utf8-boundaries", "slicing 'ℤ' and '😻' only at char boundaries", utf8_boundaries),
+    Example::new("other-slices", "&a[1..3] on an array of i32", other_slices),
+];
+
+fn main() -> ExitCode {
+    examples::run("slices", EXAMPLES)
+}
@@ -0,0 +1,614 @@
+use std::io::Write as _;
+use std::process::ExitCode;
+
+use lessons::bindings::Tracer;
+use lessons::borrowing::calculate_length;
+use lessons::chars::{CharInfo, StrReport};
+use lessons::compound;
+use lessons::examples::{self, Command, Example};
+use lessons::floats;
+use lessons::integers::literal;
+use lessons::integers::overflow::{self, Op};
+use lessons::integers::ranges::{self, Format};
+use lessons::integers::IntType;
+
+// fn main() {
+//     let x = 5;
+//     println!("The value of x is: {x}");
+//     x = 6;
+//     println!("The value of x is: {x}");
+// }
+// (doesn't compile: error[E0384], checked by compile_fail/variables_data_types/assign_twice_to_immutable.rs)
+fn mutable() {
+    let mut x = 5;
+    println!("The value of x is: {x}");
+    x = 6;
+    println!("The value of x is: {x}");
+}
+// Constants can be declared in any scope, including the global scope, which makes them useful for values that many parts of code need to know about.
+const THREE_HOURS_IN_SECONDS: u32 = 60 * 60 * 3;
+
+fn constants() {
+    println!("Three hours is {THREE_HOURS_IN_SECONDS} seconds");
+}
+// Rust’s naming convention for constants is to use all uppercase with underscores between words
+// Naming hardcoded values used throughout your program as constants is useful in conveying the meaning of that value to future maintainers of the code.
+// It also helps to have only one place in your code you would need to change if the hardcoded value needed to be updated in the future.
+
+// Shadowing
+fn shadowing() {
+    let x = 5;
+
+    let x = x + 1;
+
+    {
+        let x = x * 2;
+        println!("The value of x in the inner scope is: {x}");
+    }
+
+    println!("The value of x is: {x}");
+}
+
+// Shadowing is different from marking a variable as mut: the second let creates a new variable,
+// so it gets its own place in memory and can even have a different type.
+// lessons::bindings::Tracer records each binding's scope depth, type and address;
+// the timeline numbers every distinct address as a slot.
+fn shadowing_trace() {
+    let mut t = Tracer::new();
+
+    let x = 5;
+    t.bind("x", &x);
+
+    let x = x + 1;
+    t.bind("x", &x);
+
+    {
+        t.enter();
+        let x = x * 2;
+        t.bind("x", &x);
+        println!("The value of x in the inner scope is: {x}");
+        t.exit();
+    }
+
+    println!("The value of x is: {x}");
+    println!();
+    print!("{t}");
+}
+
+// With let mut, x = 6 overwrites the value in place: same slot, same type.
+fn mutation_trace() {
+    let mut t = Tracer::new();
+
+    let mut x = 5;
+    t.bind_mut("x", &x);
+    println!("The value of x is: {x}");
+
+    x = 6;
+    t.assign("x", &x);
+    println!("The value of x is: {x}");
+    println!();
+    print!("{t}");
+}
+
+// Because shadowing makes a new variable, it can change the type:
+//     let spaces = "   ";
+//     let spaces = spaces.len();
+// A mut variable can't:
+//     let mut spaces = "   ";
+//     spaces = spaces.len();
+// (doesn't compile: error[E0308], checked by compile_fail/variables_data_types/mut_changes_type.rs)
+fn shadow_vs_mut() {
+    let mut t = Tracer::new();
+
+    let spaces = "   ";
+    t.bind("spaces", &spaces);
+    let spaces = spaces.len();
+    t.bind("spaces", &spaces);
+
+    let mut count = 3;
+    t.bind_mut("count", &count);
+    count += 1;
+    t.assign("count", &count);
+
+    println!("spaces = {spaces}, count = {count}");
+    println!();
+    print!("{t}");
+}
+
+// Scalar Types
+
+// Data Types
+// Each signed variant can store numbers from -(2ⁿ⁻¹) to 2ⁿ⁻¹ - 1 inclusive, where n is the number of bits that variant uses.
+// So an i8 can store numbers from -(2⁷) to 2⁷ - 1, which equals -128 to 127.
+// Unsigned variants can store numbers from 0 to 2ⁿ - 1, so a u8 can store numbers from 0 to 2⁸ - 1, which equals 0 to 255.
+// Length	Signed	Unsigned
+// 8-bit	i8	u8
+// 16-bit	i16	u16
+// 32-bit	i32	u32
+// 64-bit	i64	u64
+// 128-bit	i128	u128
+// arch	isize	usize
+// Additionally, the isize and usize types depend on the architecture of the computer your program is running on,
+// which is denoted in the table as “arch”: 64 bits if you’re on a 64-bit architecture and 32 bits if you’re on a 32-bit architecture.
+// (The text above is checked against the real MIN and MAX constants by tests/integer_ranges.rs.)
+fn integer_ranges() {
+    print!("{}", ranges::table(Format::Terminal));
+}
+
+fn integer_ranges_markdown() {
+    print!("{}", ranges::table(Format::Markdown));
+}
+
+// You can write integer literals in any of the forms shown in Table
+// Note that number literals that can be multiple numeric types allow a type suffix, such as 57u8, to designate the type.
+// Number literals	Example
+// Decimal	98_222
+// Hex	0xff
+// Octal	0o77
+// Binary	0b1111_0000
+// Byte (u8 only)	b'A'
+
+// lessons::integers::literal reads each of these forms and says what type and value rustc gives it.
+// Try your own with `cargo run --bin variables_data_types -- literal 0xff 57u8 256u8`, or with no literals for a prompt.
+const LITERALS: &[&str] = &[
+    "98_222",
+    "0xff",
+    "0o77",
+    "0b1111_0000",
+    "b'A'",
+    "57u8",
+    "1_000_000i64",
+    "-128i8",
+    "256u8",
+    "0x1ff_u8",
+    "3000000000",
+];
+
+fn integer_literals() {
+    for (i, text) in LITERALS.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        match literal::parse(text) {
+            Ok(literal) => print!("{literal}"),
+            Err(error) => print!("{text}\n{error}"),
+        }
+    }
+}
+
+// Let’s say you have a variable of type u8 that can hold values between 0 and 255. If you try to change the variable to a value outside that range,
+// such as 256, integer overflow will occur, which can result in one of two behaviors.
+// When you’re compiling in debug mode, Rust includes checks for integer overflow that cause your program to panic at runtime if this behavior occurs.
+
+// To explicitly handle the possibility of overflow, you can use these families of methods provided by the standard library for primitive numeric types:
+
+// Wrap in all modes with the wrapping_* methods, such as wrapping_add.
+// Return the None value if there is overflow with the checked_* methods.
+// Return the value and a boolean indicating whether there was overflow with the overflowing_* methods.
+// Saturate at the value’s minimum or maximum values with the saturating_* methods.
+
+// lessons::integers::overflow runs an operation through all four families and shows what plain + does:
+//     cargo run --bin variables_data_types -- overflow add 200 100 u8
+fn overflow() {
+    let cases = [
+        (Op::Add, "200", "100", IntType::U8),
+        (Op::Sub, "0", "1", IntType::U32),
+        (Op::Mul, "100", "2", IntType::I8),
+        (Op::Div, "-128", "-1", IntType::I8),
+        (Op::Rem, "7", "0", IntType::U16),
+        (Op::Pow, "2", "64", IntType::U64),
+        (Op::Add, "250", "5", IntType::U8),
+    ];
+    for (i, (op, lhs, rhs, ty)) in cases.into_iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print!("{}", overflow::explore(op, lhs, rhs, ty).unwrap());
+    }
+}
+
+// Floating-Point Types
+
+fn arithmetic() {
+    // addition
+    let sum = 5 + 10;
+
+    // subtraction
+    let difference = 95.5 - 4.3;
+
+    // multiplication
+    let product = 4 * 30;
+
+    // division
+    let quotient = 56.7 / 32.2;
+    let truncated = -5 / 3; // Results in -1
+
+    // remainder
+    let remainder = 43 % 5;
+
+    println!("sum = {sum}, difference = {difference}, product = {product}");
+    println!("quotient = {quotient}, truncated = {truncated}, remainder = {remainder}");
+}
+
+// lessons::floats looks behind those results: f32 vs f64, how a float is laid out in bits,
+// infinity and NaN, and how -5 / 3 rounds compared with div_euclid and rem_euclid.
+fn float_precision() {
+    print!("{}", floats::precision_report());
+}
+
+fn float_bits() {
+    print!("{}", floats::layout_report());
+}
+
+fn float_special_values() {
+    print!("{}", floats::special_values_report());
+}
+
+fn division_rounding() {
+    print!("{}", floats::division_report());
+}
+
+// boolean
+
+fn booleans() {
+    let t = true;
+
+    let f: bool = false; // with explicit type annotation
+
+    println!("t = {t}, f = {f}");
+}
+
+// The Character Type
+
+fn characters() {
+    let c = 'z';
+    let z: char = 'ℤ'; // with explicit type annotation
+    let heart_eyed_cat = '😻';
+
+    println!("c = {c}, z = {z}, heart_eyed_cat = {heart_eyed_cat}");
+}
+// Note that we specify char literals with single quotes, as opposed to string literals, which use double quotes.
+// Rust’s char type is four bytes in size and represents a Unicode Scalar Value, which means it can represent a lot more than just ASCII
+
+// lessons::chars shows how each of those chars is encoded:
+//     cargo run --bin variables_data_types -- chars 'ℤ'
+fn char_inspector() {
+    for c in ['z', 'ℤ', '😻'] {
+        print!("{}", CharInfo::of(c));
+    }
+}
+
+// In a String the same chars take 1, 3 and 4 bytes, so String::len() (what calculate_length returns) counts bytes, not chars.
+fn bytes_vs_chars() {
+    let s = String::from("zℤ😻");
+    println!("calculate_length(&s) = {}", calculate_length(&s));
+    print!("{}", StrReport::of(&s));
+}
+
+// Compound Types
+
+// The Tuple Type
+// A tuple is a general way of grouping together a number of values with a variety of types into one compound type.
+// Tuples have a fixed length: once declared, they cannot grow or shrink in size.
+
+fn tuple_type() {
+    let tup: (i32, f64, u8) = (500, 6.4, 1);
+
+    println!("tup = {tup:?}");
+}
+// The variable tup binds to the entire tuple because a tuple is considered a single compound element.
+// To get the individual values out of a tuple, we can use pattern matching to destructure a tuple value, like this:
+
+// // destructuring
+fn tuple() {
+    let tup = (500, 6.4, 1);
+
+    let (x, y, z) = tup;
+
+    println!("The value of y is: {y}");
+    println!("(x and z are {x} and {z})");
+}
+
+// Patterns nest: a tuple holding a tuple and an array comes apart in one let.
+// The pattern has to have the same shape as the value:
+// let (x, y) = (500, 6.4, 1);
+// (doesn't compile: error[E0308], checked by compile_fail/variables_data_types/tuple_pattern_wrong_arity.rs)
+fn nested_destructure() {
+    let ((x, y), [a, b, c], name) = ((500, 6.4), [1u8, 2, 3], "tup");
+    println!("x = {x}, y = {y}, a = {a}, b = {b}, c = {c}, name = {name}");
+
+    let points = [(1, 2), (3, 4), (5, 6)];
+    for &(x, y) in &points {
+        println!("point ({x}, {y})");
+    }
+    println!("sum_points = {:?}", compound::sum_points(&points));
+    println!("flatten = {:?}", compound::flatten(((500, 6.4), [1, 2, 3], "tup")));
+}
+
+// period (.)
+fn tuple_index() {
+    let x: (i32, f64, u8) = (500, 6.4, 1);
+
+    let five_hundred = x.0;
+
+    let six_point_four = x.1;
+
+    let one = x.2;
+
+    println!("{five_hundred}, {six_point_four}, {one}");
+}
+
+// The Array Type
+
+// Unlike a tuple, every element of an array must have the same type.
+// Unlike arrays in some other languages, arrays in Rust have a fixed length.
+fn array() {
+    let a = [1, 2, 3, 4, 5];
+
+    println!("a = {a:?}");
+}
+// Arrays are useful when you want your data allocated on the stack rather than the heap
+
+// An array isn’t as flexible as the vector type, though.
+// A vector is a similar collection type provided by the standard library that is allowed to grow or shrink in size.
+// You write an array’s type using square brackets with the type of each element, a semicolon, and then the number of elements in the array
+// let a: [i32; 5] = [1, 2, 3, 4, 5];
+
+// You can also initialize an array to contain the same value for each element by specifying the initial value, followed by a semicolon,
+// let a = [3; 5];
+// The value is copied into every slot, so it has to be Copy (or a constant):
+// let strings = [String::new(); 3];
+// (doesn't compile: error[E0277], checked by compile_fail/variables_data_types/repeat_non_copy.rs)
+fn array_repeat() {
+    let a = [3; 5];
+    let zeros: [u8; 4] = compound::filled(0);
+    let flags = [false; 3];
+
+    println!("[3; 5] = {a:?}, filled(0) = {zeros:?}, [false; 3] = {flags:?}");
+}
+
+// Accessing Array Elements
+// An array is a single chunk of memory of a known, fixed size that can be allocated on the stack.
+
+fn array_access() {
+    let a = [1, 2, 3, 4, 5];
+
+    let first = a[0];
+    let second = a[1];
+
+    println!("first = {first}, second = {second}");
+}
+
+// memory safety principles in action. In many low-level languages, this kind of check is not done, and when you provide an incorrect index,
+// invalid memory can be accessed.
+// Rust protects you against this kind of error by immediately exiting instead of allowing the memory access and continuing
+
+// get does the same bounds check but returns an Option, so a bad index is a value to handle rather than a crash.
+fn array_get() {
+    let a = [1, 2, 3, 4, 5];
+
+    for index in [0, 4, 5, 10] {
+        match compound::element(&a, index) {
+            Ok(element) => println!("a.get({index}) = Some({element})"),
+            Err(error) => println!("a.get({index}) = None ({error})"),
+        }
+    }
+}
+
+// What a[index] does with a bad index: the panic is caught here so the example can report it and carry on
+// (its usual message still appears on stderr).
+fn array_out_of_bounds() {
+    let a = [1, 2, 3, 4, 5];
+
+    for index in [2, 10] {
+        match compound::index_catching_panic(&a, index) {
+            Ok(element) => println!("a[{index}] = {element}"),
+            Err(message) => println!("a[{index}] panicked: {message}"),
+        }
+    }
+}
+
+// Slice patterns match on the length and bind elements at either end; .. skips the rest.
+fn slice_patterns() {
+    let a = [1, 2, 3, 4, 5];
+
+    // An array's length is part of its type, so this pattern can't fail.
+    let [first, .., last] = a;
+    println!("first = {first}, last = {last}");
+    for s in [&a[..0], &a[..1], &a[..2], &a[..]] {
+        println!("{s:?}: {}", compound::shape(s));
+    }
+    println!("first_and_last = {:?}", compound::first_and_last(&a));
+    println!("head_tail = {:?}", compound::head_tail(&a));
+}
+
+const EXAMPLES: &[Example] = &[
+    Example::new("mutable", "let mut x lets x = 6 compile", mutable),
+    Example::new("constants", "const THREE_HOURS_IN_SECONDS", constants),
+    Example::new("shadowing", "let x = x + 1 shadows x in nested scopes", shadowing),
+    Example::new(
+        "shadowing-trace",
+        "the shadowing example with each binding's depth, type and address",
+        shadowing_trace,
+    ),
+    Example::new("mutation-trace", "let mut x: assignment reuses the same slot", mutation_trace),
+    Example::new("shadow-vs-mut", "shadowing can change the type, mut can't", shadow_vs_mut),
+    Example::new("arithmetic", "+, -, *, / and % on integers and floats", arithmetic),
+    Example::new("integer-ranges", "every integer type's MIN to MAX, from the real constants", integer_ranges),
+    Example::new("integer-ranges-markdown", "the same table as Markdown", integer_ranges_markdown),
+    Example::new("integer-literals", "decimal, hex, octal, binary, byte and suffixed literals", integer_literals),
+    Example::new(
+        "overflow-strategies",
+        "wrapping, checked, overflowing and saturating on out-of-range results",
+        overflow,
+    ),
+    Example::new("float-precision", "the same expressions in f32 and f64", float_precision),
+    Example::new("float-bits", "sign, exponent and mantissa of an f64 and an f32", float_bits),
+    Example::new("float-special-values", "infinity, NaN and -0.0 rules", float_special_values),
+    Example::new("division-rounding", "/ and % on negatives vs div_euclid and rem_euclid", division_rounding),
+    Example::new("booleans", "bool with and without a type annotation", booleans),
+    Example::new("characters", "char literals 'z', 'ℤ' and '😻'", characters),
+    Example::new("char-inspector", "code point, UTF-8, UTF-16 and category of 'z', 'ℤ' and '😻'", char_inspector),
+    Example::new("bytes-vs-chars", "len() counts UTF-8 bytes, chars().count() counts chars", bytes_vs_chars),
+    Example::new("tuple", "a tuple of (i32, f64, u8)", tuple_type),
+    Example::new("tuple-destructure", "let (x, y, z) = tup", tuple),
+    Example::new("nested-destructure", "tuples inside tuples and arrays in one let, and in a for", nested_destructure),
+    Example::new("tuple-index", "x.0, x.1 and x.2 field access", tuple_index),
+    Example::new("array", "an array of five integers", array),
+    Example::new("array-repeat", "[3; 5] and other repeat expressions", array_repeat),
+    Example::new("array-access", "a[0] and a[1] indexing", array_access),
+    Example::new("array-get", "a.get(i) returns None past the end", array_get),
+    Example::new("array-out-of-bounds", "a[10] panics; the panic is caught and reported", array_out_of_bounds),
+    Example::new("slice-patterns", "[first, .., last] and other slice patterns", slice_patterns),
+];
+
+fn overflow_command(args: &[String]) -> ExitCode {
+    let [op, lhs, rhs, ty] = args else {
+        eprintln!("usage: variables_data_types overflow OP A B TYPE   (e.g. overflow add 200 100 u8)");
+        return ExitCode::from(2);
+    };
+    let op = match op.parse::<Op>() {
+        Ok(op) => op,
+        Err(e) => {
+            eprintln!("overflow: {e}");
+            return ExitCode::from(2);
+        }
+    };
+    let ty = match ty.parse::<IntType>() {
+        Ok(ty) => ty,
+        Err(e) => {
+            eprintln!("overflow: {e}");
+            return ExitCode::from(2);
+        }
+    };
+    match overflow::explore(op, lhs, rhs, ty) {
+        Ok(exploration) => {
+            print!("{exploration}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("overflow: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Prints what each literal is; with none given, asks for them one per line.
+fn literal_command(args: &[String]) -> ExitCode {
+    let describe = |text: &str| match literal::parse(text) {
+        Ok(literal) => {
+            print!("{literal}");
+            true
+        }
+        Err(error) => {
+            print!("{text}\n{error}");
+            false
+        }
+    };
+
+    if !args.is_empty() {
+        let mut all_ok = true;
+        for (i, text) in args.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            all_ok &= describe(text);
+        }
+        return if all_ok { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+    }
+
+    let stdin = std::io::stdin();
+    let mut line = String::new();
+    loop {
+        print!("literal> ");
+        let _ = std::io::stdout().flush();
+        line.clear();
+        match stdin.read_line(&mut line) {
+            Ok(0) => {
+                println!();
+                return ExitCode::SUCCESS;
+            }
+            Ok(_) if line.trim().is_empty() => {}
+            Ok(_) => {
+                describe(line.trim());
+            }
+            Err(e) => {
+                eprintln!("literal: {e}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+}
+
+/// One char in detail, or a table for anything longer.
+fn chars_command(args: &[String]) -> ExitCode {
+    if args.is_empty() {
+        eprintln!("usage: variables_data_types chars TEXT...");
+        return ExitCode::from(2);
+    }
+    for (i, text) in args.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => print!("{}", CharInfo::of(c)),
+            _ => print!("{}", StrReport::of(text)),
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn floats_command(args: &[String]) -> ExitCode {
+    if args.is_empty() {
+        print!("{}", floats::report());
+        return ExitCode::SUCCESS;
+    }
+    for (i, arg) in args.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        match arg.parse::<f64>() {
+            Ok(x) => {
+                print!("{}", floats::describe_f64(x));
+                print!("{}", floats::describe_f32(x as f32));
+            }
+            Err(e) => {
+                eprintln!("floats: `{arg}`: {e}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+const COMMANDS: &[Command] = &[
+    Command::new(
+        "chars",
+        "TEXT...",
+        "code point, UTF-8 and UTF-16 of a char, or of every char in a string",
+        chars_command,
+    ),
+    Command::new(
+        "floats",
+        "[VALUE...]",
+        "bit layout of each VALUE as f64 and f32, or the whole float report",
+        floats_command,
+    ),
+    Command::new(
+        "literal",
+        "[LITERAL...]",
+        "what type and value a literal has (asks for literals if none are given)",
+        literal_command,
+    ),
+    Command::new(
+        "overflow",
+        "OP A B TYPE",
+        "A op B as TYPE under every overflow strategy (OP: add sub mul div rem pow)",
+        overflow_command,
+    ),
+];
+
+fn main() -> ExitCode {
+    examples::run_with_commands("variables_data_types", EXAMPLES, COMMANDS)
+}
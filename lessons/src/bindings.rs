@@ -0,0 +1,244 @@
+// Tracing bindings
+// `let x = x + 1;` and `x = x + 1;` print the same numbers, but they do
+// different things. Shadowing declares a new variable: it gets its own slot
+// on the stack and may have a different type, while the old one is hidden
+// until the scope that shadowed it ends. Assigning to a `let mut` binding
+// writes a new value into the slot that's already there, and the type can't
+// change.
+//
+// A `Tracer` is told about each `let`, assignment and block as the example
+// runs, with a reference to the variable itself, and records the scope depth,
+// the type and the variable's address:
+//
+//     let mut t = Tracer::new();
+//     let x = 5;
+//     t.bind("x", &x);
+//     let x = x + 1;
+//     t.bind("x", &x);
+//     println!("{t}");
+//
+// The timeline numbers each distinct address as a slot, so a shadow shows up
+// as a new slot and an assignment as the same one.
+
+use std::any::type_name;
+use std::fmt;
+
+/// One variable as the tracer saw it when it was declared.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Binding {
+    pub name: String,
+    pub depth: usize,
+    pub type_name: &'static str,
+    pub address: usize,
+    pub mutable: bool,
+}
+
+/// A step of the timeline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// `let`, possibly hiding an earlier binding with the same name.
+    Let { binding: Binding, value: String, shadows: Option<Binding> },
+    /// `name = value` on a live binding; `address` is where the new value was
+    /// found afterwards.
+    Assign { binding: Binding, value: String, address: usize },
+    /// `{`: `depth` is the depth inside the new block.
+    Enter { depth: usize },
+    /// `}`: the bindings declared in the block go away, and whatever they
+    /// shadowed is visible again.
+    Exit { depth: usize, dropped: Vec<Binding>, uncovered: Vec<Binding> },
+}
+
+/// Records bindings, assignments and scopes as an example runs.
+#[derive(Default)]
+pub struct Tracer {
+    depth: usize,
+    live: Vec<Binding>,
+    events: Vec<Event>,
+}
+
+impl Tracer {
+    pub fn new() -> Self {
+        Tracer::default()
+    }
+
+    /// Records `let name = value;`, given the new variable.
+    pub fn bind<T: fmt::Debug>(&mut self, name: &str, value: &T) {
+        self.declare(name, value, false);
+    }
+
+    /// Records `let mut name = value;`.
+    pub fn bind_mut<T: fmt::Debug>(&mut self, name: &str, value: &T) {
+        self.declare(name, value, true);
+    }
+
+    fn declare<T: fmt::Debug>(&mut self, name: &str, value: &T, mutable: bool) {
+        let binding = Binding {
+            name: name.to_string(),
+            depth: self.depth,
+            type_name: type_name::<T>(),
+            address: value as *const T as usize,
+            mutable,
+        };
+        let shadows = self.visible(name).cloned();
+        self.live.push(binding.clone());
+        self.events.push(Event::Let { binding, value: format!("{value:?}"), shadows });
+    }
+
+    /// Records `name = value;`, given the variable after the assignment.
+    ///
+    /// Panics if no binding called `name` is in scope.
+    pub fn assign<T: fmt::Debug>(&mut self, name: &str, value: &T) {
+        let binding = self.visible(name).cloned().unwrap_or_else(|| panic!("no binding `{name}` in scope"));
+        let address = value as *const T as usize;
+        self.events.push(Event::Assign { binding, value: format!("{value:?}"), address });
+    }
+
+    /// Records the start of a block.
+    pub fn enter(&mut self) {
+        self.depth += 1;
+        self.events.push(Event::Enter { depth: self.depth });
+    }
+
+    /// Records the end of the innermost block.
+    pub fn exit(&mut self) {
+        assert!(self.depth > 0, "exit without a matching enter");
+        let split = self.live.iter().position(|b| b.depth == self.depth).unwrap_or(self.live.len());
+        let dropped = self.live.split_off(split);
+        let uncovered = dropped.iter().filter_map(|b| self.visible(&b.name).cloned()).collect::<Vec<_>>();
+        let mut unique: Vec<Binding> = Vec::new();
+        for binding in uncovered {
+            if !unique.contains(&binding) {
+                unique.push(binding);
+            }
+        }
+        self.events.push(Event::Exit { depth: self.depth, dropped, uncovered: unique });
+        self.depth -= 1;
+    }
+
+    /// The binding `name` currently refers to, if any.
+    pub fn visible(&self, name: &str) -> Option<&Binding> {
+        self.live.iter().rev().find(|b| b.name == name)
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Distinct addresses in the order they first appear; a binding's slot
+    /// number is its position here, counting from 1.
+    pub fn slots(&self) -> Vec<usize> {
+        let mut slots = Vec::new();
+        for event in &self.events {
+            let address = match event {
+                Event::Let { binding, .. } => binding.address,
+                Event::Assign { address, .. } => *address,
+                Event::Enter { .. } | Event::Exit { .. } => continue,
+            };
+            if !slots.contains(&address) {
+                slots.push(address);
+            }
+        }
+        slots
+    }
+
+    pub fn slot_of(&self, address: usize) -> Option<usize> {
+        self.slots().iter().position(|&a| a == address).map(|i| i + 1)
+    }
+}
+
+impl fmt::Display for Tracer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let slot = |address: usize| self.slot_of(address).map_or(String::new(), |n| n.to_string());
+        let described = |b: &Binding| format!("{} (slot {})", b.name, slot(b.address));
+        let indent = |depth: usize| "    ".repeat(depth);
+
+        let rows: Vec<[String; 6]> = self
+            .events
+            .iter()
+            .map(|event| match event {
+                Event::Let { binding, value, shadows } => {
+                    let keyword = if binding.mutable { "let mut" } else { "let" };
+                    let note = match shadows {
+                        None => "new binding".to_string(),
+                        Some(old) => {
+                            // The compiler may reuse a dead binding's stack slot.
+                            let place = if old.address != binding.address { "new slot" } else { "same slot" };
+                            let mut note = format!("{place}, shadows {}", described(old));
+                            if old.type_name != binding.type_name {
+                                note.push_str(&format!("; type {} -> {}", old.type_name, binding.type_name));
+                            }
+                            note
+                        }
+                    };
+                    [
+                        binding.depth.to_string(),
+                        format!("{}{keyword} {} = {value}", indent(binding.depth), binding.name),
+                        binding.type_name.to_string(),
+                        format!("{:#018x}", binding.address),
+                        slot(binding.address),
+                        note,
+                    ]
+                }
+                Event::Assign { binding, value, address } => {
+                    let note = if *address == binding.address {
+                        "same slot, value overwritten".to_string()
+                    } else {
+                        format!("written at a different address from {}", described(binding))
+                    };
+                    [
+                        binding.depth.to_string(),
+                        format!("{}{} = {value}", indent(binding.depth), binding.name),
+                        binding.type_name.to_string(),
+                        format!("{address:#018x}"),
+                        slot(*address),
+                        note,
+                    ]
+                }
+                Event::Enter { depth } => [
+                    depth.to_string(),
+                    format!("{}{{", indent(depth - 1)),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                ],
+                Event::Exit { depth, dropped, uncovered } => {
+                    let mut notes: Vec<String> = Vec::new();
+                    if !dropped.is_empty() {
+                        let names: Vec<String> = dropped.iter().map(&described).collect();
+                        notes.push(format!("{} out of scope", names.join(", ")));
+                    }
+                    if !uncovered.is_empty() {
+                        let names: Vec<String> = uncovered.iter().map(&described).collect();
+                        notes.push(format!("{} visible again", names.join(", ")));
+                    }
+                    [
+                        depth.to_string(),
+                        format!("{}}}", indent(depth - 1)),
+                        String::new(),
+                        String::new(),
+                        String::new(),
+                        notes.join("; "),
+                    ]
+                }
+            })
+            .collect();
+
+        let header = ["depth", "code", "type", "address", "slot", "note"];
+        let mut widths = header.map(|h| h.chars().count());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let line = |cells: [&str; 6]| -> String {
+            let padded: Vec<String> = cells.iter().zip(widths).map(|(cell, w)| format!("{cell:w$}")).collect();
+            padded.join("  ").trim_end().to_string()
+        };
+        writeln!(f, "{}", line(header))?;
+        for row in &rows {
+            writeln!(f, "{}", line(row.each_ref().map(String::as_str)))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,784 @@
+// The checker walks each function once, tracking for every variable whether it
+// is initialised, moved or borrowed, and which loans (borrows) are alive.
+//
+// A loan is alive while the reference holding it will still be used, as with
+// non-lexical lifetimes. To know "will still be used" during a single forward
+// walk, the program is walked twice: the first walk only records the last use
+// of every variable, the second walk reports errors using that knowledge.
+
+use std::collections::HashMap;
+
+use super::parser::{Block, Expr, Function, Ident, Program, Stmt, Type};
+use super::{Diagnostic, Span};
+
+type VarId = usize;
+type LoanId = usize;
+
+#[derive(Clone, Copy, Debug)]
+enum State {
+    Uninit,
+    Init,
+    Moved(Span),
+}
+
+struct Var {
+    name: String,
+    ty: Option<Type>,
+    mutable: bool,
+    state: State,
+    decl: Span,
+    is_param: bool,
+    /// Loans this variable holds, i.e. what it is a reference to.
+    loans: Vec<LoanId>,
+}
+
+struct Loan {
+    borrowed: VarId,
+    mutable: bool,
+    span: Span,
+}
+
+/// The result of an expression: its type (unknown after an error) and the
+/// loans it carries if it is a reference.
+struct Value {
+    ty: Option<Type>,
+    loans: Vec<LoanId>,
+}
+
+impl Value {
+    fn of(ty: Type) -> Self {
+        Value { ty: Some(ty), loans: Vec::new() }
+    }
+
+    fn unknown() -> Self {
+        Value { ty: None, loans: Vec::new() }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    Inner,
+    /// References in the tail value are returned from the function. Whether
+    /// they may point at locals is only checked when the signature allows
+    /// returning a reference at all (`check_return`).
+    FunctionBody {
+        check_return: bool,
+    },
+}
+
+struct Signature {
+    params: Vec<Type>,
+    ret: Type,
+}
+
+pub fn check(program: &Program) -> Vec<Diagnostic> {
+    let mut signatures = HashMap::new();
+    let mut diagnostics = Vec::new();
+    for function in &program.functions {
+        let signature =
+            Signature { params: function.params.iter().map(|(_, ty)| ty.clone()).collect(), ret: function.ret.clone() };
+        if signatures.insert(function.name.name.clone(), signature).is_some() {
+            diagnostics.push(Diagnostic::error(
+                Some("E0428"),
+                format!("the name `{}` is defined multiple times", function.name.name),
+                function.name.span,
+                "redefined here",
+            ));
+        }
+    }
+
+    // First walk: only collect last uses.
+    let mut first = Checker::new(&signatures, HashMap::new());
+    for function in &program.functions {
+        first.function(function);
+    }
+
+    let mut second = Checker::new(&signatures, first.uses);
+    for function in &program.functions {
+        second.function(function);
+    }
+    diagnostics.extend(second.diagnostics);
+    diagnostics
+}
+
+struct Checker<'s> {
+    signatures: &'s HashMap<String, Signature>,
+    vars: Vec<Var>,
+    scopes: Vec<Vec<VarId>>,
+    loans: Vec<Loan>,
+    /// Loans made by the current statement that no variable holds yet,
+    /// e.g. the `&s` in `change(&s);`.
+    temps: Vec<LoanId>,
+    /// Last use of each variable, from the first walk.
+    last_use: HashMap<VarId, Span>,
+    /// Last use of each variable seen in this walk.
+    uses: HashMap<VarId, Span>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'s> Checker<'s> {
+    fn new(signatures: &'s HashMap<String, Signature>, last_use: HashMap<VarId, Span>) -> Self {
+        Checker {
+            signatures,
+            vars: Vec::new(),
+            scopes: Vec::new(),
+            loans: Vec::new(),
+            temps: Vec::new(),
+            last_use,
+            uses: HashMap::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn error(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    // ---- variables and loans ----
+
+    fn declare(&mut self, name: &Ident, var: Var) -> VarId {
+        let id = self.vars.len();
+        self.vars.push(Var { name: name.name.clone(), ..var });
+        self.scopes.last_mut().expect("inside a scope").push(id);
+        id
+    }
+
+    fn lookup(&self, name: &str) -> Option<VarId> {
+        // Innermost first, and the latest `let` in a scope shadows earlier ones.
+        self.scopes.iter().rev().find_map(|scope| scope.iter().rev().copied().find(|&id| self.vars[id].name == name))
+    }
+
+    fn lookup_or_report(&mut self, ident: &Ident) -> Option<VarId> {
+        let found = self.lookup(&ident.name);
+        if found.is_none() {
+            self.error(Diagnostic::error(
+                Some("E0425"),
+                format!("cannot find value `{}` in this scope", ident.name),
+                ident.span,
+                "not found in this scope",
+            ));
+        }
+        found
+    }
+
+    fn record_use(&mut self, id: VarId, span: Span) {
+        let last = self.uses.entry(id).or_insert(span);
+        if span.start > last.start {
+            *last = span;
+        }
+    }
+
+    /// A live loan on `var` at `pos`, with the later use that keeps it alive
+    /// (`None` when a temporary in the current statement holds it).
+    fn live_loans_on(&self, var: VarId, pos: usize) -> Vec<(LoanId, Option<Span>)> {
+        let mut live = Vec::new();
+        for (id, loan) in self.loans.iter().enumerate() {
+            if loan.borrowed != var {
+                continue;
+            }
+            if self.temps.contains(&id) {
+                live.push((id, None));
+                continue;
+            }
+            let later_use = self
+                .scopes
+                .iter()
+                .flatten()
+                .filter(|&&holder| self.vars[holder].loans.contains(&id))
+                .filter_map(|holder| self.last_use.get(holder).copied())
+                .filter(|used| used.start > pos)
+                .max_by_key(|used| used.start);
+            if let Some(used) = later_use {
+                live.push((id, Some(used)));
+            }
+        }
+        live
+    }
+
+    fn with_later_use(diagnostic: Diagnostic, later: Option<Span>, text: &str) -> Diagnostic {
+        match later {
+            Some(span) => diagnostic.with_label(span, text),
+            None => diagnostic,
+        }
+    }
+
+    /// Checks that `id` holds a value that can be read at `span`.
+    fn check_initialized(&mut self, id: VarId, span: Span, verb: &str) -> bool {
+        let var = &self.vars[id];
+        let name = var.name.clone();
+        match var.state {
+            State::Init => true,
+            State::Uninit => {
+                let decl = var.decl;
+                self.error(
+                    Diagnostic::error(
+                        Some("E0381"),
+                        format!("used binding `{name}` isn't initialized"),
+                        span,
+                        &format!("`{name}` used here but it isn't initialized"),
+                    )
+                    .with_label(decl, "binding declared here but left uninitialized"),
+                );
+                false
+            }
+            State::Moved(moved_at) => {
+                let (kind, label) = match verb {
+                    "borrow" => ("borrow", "value borrowed here after move"),
+                    _ => ("use", "value used here after move"),
+                };
+                self.error(
+                    Diagnostic::error(Some("E0382"), format!("{kind} of moved value: `{name}`"), span, label)
+                        .with_label(moved_at, "value moved here"),
+                );
+                false
+            }
+        }
+    }
+
+    // ---- functions and blocks ----
+
+    fn function(&mut self, function: &Function) {
+        self.scopes.push(Vec::new());
+        for (param, ty) in &function.params {
+            self.declare(
+                param,
+                Var {
+                    name: String::new(),
+                    ty: Some(ty.clone()),
+                    mutable: false,
+                    state: State::Init,
+                    decl: param.span,
+                    is_param: true,
+                    loans: Vec::new(),
+                },
+            );
+        }
+
+        // Elision gives the return type the lifetime of the parameters only
+        // when they have exactly one (there's no `&self` here).
+        let returns_reference = function.ret.is_reference();
+        let input_lifetimes: usize = function.params.iter().map(|(_, ty)| ty.lifetimes()).sum();
+        let elision_fails = returns_reference && input_lifetimes != 1;
+        if elision_fails {
+            self.error(
+                Diagnostic::error(
+                    Some("E0106"),
+                    "missing lifetime specifier",
+                    function.ret_span,
+                    "expected named lifetime parameter",
+                )
+                .with_note(elision_help(function, input_lifetimes)),
+            );
+        }
+
+        let value = self.block(&function.body, BlockKind::FunctionBody { check_return: !elision_fails });
+        match (&function.body.tail, &value.ty) {
+            (None, _) if function.ret != Type::Unit => self.error(Diagnostic::error(
+                Some("E0308"),
+                "mismatched types",
+                function.ret_span,
+                &format!("expected `{}`, found `()`", function.ret),
+            )),
+            (Some(tail), Some(ty)) if *ty != function.ret => self.error(Diagnostic::error(
+                Some("E0308"),
+                "mismatched types",
+                tail.span(),
+                &format!("expected `{}`, found `{ty}`", function.ret),
+            )),
+            _ => {}
+        }
+        self.end_scope(function.body.close);
+        self.scopes.pop();
+    }
+
+    /// Checks a block. A function body shares its scope with the parameters,
+    /// which `function` has already opened.
+    fn block(&mut self, block: &Block, kind: BlockKind) -> Value {
+        let inner = kind == BlockKind::Inner;
+        if inner {
+            self.scopes.push(Vec::new());
+        }
+
+        for stmt in &block.stmts {
+            self.stmt(stmt);
+            self.temps.clear();
+        }
+        let value = match &block.tail {
+            Some(tail) => self.expr(tail),
+            None => Value::of(Type::Unit),
+        };
+
+        // References to this block's locals must not escape through its value.
+        let locals: Vec<VarId> = self.scopes.last().cloned().unwrap_or_default();
+        for &loan_id in &value.loans {
+            let loan = &self.loans[loan_id];
+            if !locals.contains(&loan.borrowed) {
+                continue;
+            }
+            let var = &self.vars[loan.borrowed];
+            let name = var.name.clone();
+            let span = loan.span;
+            if let BlockKind::FunctionBody { check_return } = kind {
+                if check_return {
+                    let what = if var.is_param { "function parameter" } else { "local variable" };
+                    self.error(Diagnostic::error(
+                        Some("E0515"),
+                        format!("cannot return reference to {what} `{name}`"),
+                        span,
+                        "returns a reference to data owned by the current function",
+                    ));
+                }
+            } else {
+                self.error(
+                    Diagnostic::error(
+                        Some("E0597"),
+                        format!("`{name}` does not live long enough"),
+                        span,
+                        "borrowed value does not live long enough",
+                    )
+                    .with_label(block.close, format!("`{name}` dropped here while still borrowed")),
+                );
+            }
+        }
+
+        if inner {
+            self.end_scope(block.close);
+            self.scopes.pop();
+        }
+        value
+    }
+
+    /// Locals of the innermost scope are dropped at `close`: any loan on
+    /// them that an outer reference still uses later is dangling.
+    fn end_scope(&mut self, close: Span) {
+        let locals: Vec<VarId> = self.scopes.last().cloned().unwrap_or_default();
+        let outer: Vec<VarId> = self.scopes[..self.scopes.len() - 1].iter().flatten().copied().collect();
+        for &local in &locals {
+            for (loan_id, loan) in self.loans.iter().enumerate() {
+                if loan.borrowed != local {
+                    continue;
+                }
+                let later_use = outer
+                    .iter()
+                    .filter(|&&holder| self.vars[holder].loans.contains(&loan_id))
+                    .filter_map(|holder| self.last_use.get(holder).copied())
+                    .find(|used| used.start > close.start);
+                if let Some(used) = later_use {
+                    let name = &self.vars[local].name;
+                    let diagnostic = Diagnostic::error(
+                        Some("E0597"),
+                        format!("`{name}` does not live long enough"),
+                        loan.span,
+                        "borrowed value does not live long enough",
+                    )
+                    .with_label(close, format!("`{name}` dropped here while still borrowed"))
+                    .with_label(used, "borrow later used here");
+                    self.diagnostics.push(diagnostic);
+                }
+            }
+        }
+        for &local in &locals {
+            self.vars[local].loans.clear();
+        }
+    }
+
+    // ---- statements ----
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Let { name, mutable, init } => {
+                let (ty, loans, state) = match init {
+                    Some(init) => {
+                        let value = self.expr(init);
+                        (value.ty, value.loans, State::Init)
+                    }
+                    None => (None, Vec::new(), State::Uninit),
+                };
+                self.declare(
+                    name,
+                    Var { name: String::new(), ty, mutable: *mutable, state, decl: name.span, is_param: false, loans },
+                );
+            }
+            Stmt::Assign { target, deref: false, value } => self.assign(target, value),
+            Stmt::Assign { target, deref: true, value } => self.assign_through(target, value),
+            Stmt::Expr(expr) => {
+                self.expr(expr);
+            }
+        }
+    }
+
+    fn assign(&mut self, target: &Ident, value: &Expr) {
+        let value_span = value.span();
+        let value = self.expr(value);
+        let Some(id) = self.lookup_or_report(target) else {
+            return;
+        };
+        let span = target.span.to(value_span);
+        let var = &self.vars[id];
+        let name = var.name.clone();
+
+        if !matches!(var.state, State::Uninit) && !var.mutable {
+            let decl = var.decl;
+            self.error(
+                Diagnostic::error(
+                    Some("E0384"),
+                    format!("cannot assign twice to immutable variable `{name}`"),
+                    span,
+                    "cannot assign twice to immutable variable",
+                )
+                .with_label(decl, format!("first assignment to `{name}`"))
+                .with_note(format!("help: consider making this binding mutable: `mut {name}`")),
+            );
+        }
+        if let Some(&(loan_id, later)) = self.live_loans_on(id, span.start).first() {
+            let borrowed_at = self.loans[loan_id].span;
+            let diagnostic = Diagnostic::error(
+                Some("E0506"),
+                format!("cannot assign to `{name}` because it is borrowed"),
+                span,
+                &format!("`{name}` is assigned to here but it was already borrowed"),
+            )
+            .with_label(borrowed_at, format!("`{name}` is borrowed here"));
+            self.error(Checker::with_later_use(diagnostic, later, "borrow later used here"));
+        }
+        if let (Some(expected), Some(found)) = (&self.vars[id].ty, &value.ty) {
+            if expected != found {
+                let label = format!("expected `{expected}`, found `{found}`");
+                self.error(Diagnostic::error(Some("E0308"), "mismatched types", value_span, &label));
+                return;
+            }
+        }
+
+        let var = &mut self.vars[id];
+        var.state = State::Init;
+        if var.ty.is_none() {
+            var.ty = value.ty;
+        }
+        var.loans = value.loans; // the old borrow, if any, is over
+    }
+
+    fn assign_through(&mut self, target: &Ident, value: &Expr) {
+        let value_span = value.span();
+        let value = self.expr(value);
+        let Some(id) = self.lookup_or_report(target) else {
+            return;
+        };
+        self.record_use(id, target.span);
+        if !self.check_initialized(id, target.span, "use") {
+            return;
+        }
+        let name = self.vars[id].name.clone();
+        let span = Span::new(target.span.start - 1, value_span.end);
+        match self.vars[id].ty.clone() {
+            Some(Type::RefMut(inner)) => {
+                if let Some(found) = &value.ty {
+                    if *found != *inner {
+                        let label = format!("expected `{inner}`, found `{found}`");
+                        self.error(Diagnostic::error(Some("E0308"), "mismatched types", value_span, &label));
+                    }
+                }
+            }
+            Some(Type::Ref(_)) => self.error(Diagnostic::error(
+                Some("E0594"),
+                format!("cannot assign to `*{name}`, which is behind a `&` reference"),
+                span,
+                &format!("`{name}` is a `&` reference, so the data it refers to cannot be written"),
+            )),
+            Some(ty) => self.error(Diagnostic::error(
+                Some("E0614"),
+                format!("type `{ty}` cannot be dereferenced"),
+                target.span,
+                "",
+            )),
+            None => {}
+        }
+    }
+
+    // ---- expressions ----
+
+    fn expr(&mut self, expr: &Expr) -> Value {
+        match expr {
+            Expr::Int(_) => Value::of(Type::I32),
+            Expr::StringFrom(_) => Value::of(Type::String),
+            Expr::Var(ident) => self.use_var(ident, true),
+            Expr::Borrow { mutable, var, span } => self.borrow(var, *mutable, *span),
+            Expr::Deref { var, span } => self.deref(var, *span, false),
+            Expr::Call { name, args, span } => self.call(name, args, *span),
+            Expr::Block(block) => self.block(block, BlockKind::Inner),
+        }
+    }
+
+    /// Reads a variable. With `moves` set, a non-Copy value moves out of it.
+    fn use_var(&mut self, ident: &Ident, moves: bool) -> Value {
+        let Some(id) = self.lookup_or_report(ident) else {
+            return Value::unknown();
+        };
+        self.record_use(id, ident.span);
+        let verb = if moves { "use" } else { "borrow" };
+        if !self.check_initialized(id, ident.span, verb) {
+            return Value { ty: self.vars[id].ty.clone(), loans: Vec::new() };
+        }
+
+        let ty = self.vars[id].ty.clone();
+        let copies = ty.as_ref().is_none_or(Type::is_copy);
+        if !moves || copies {
+            if !moves {
+                self.check_not_mutably_borrowed(id, ident.span);
+            }
+            let loans = self.vars[id].loans.clone();
+            self.temps.extend(&loans);
+            return Value { ty, loans };
+        }
+
+        if let Some(&(loan_id, later)) = self.live_loans_on(id, ident.span.start).first() {
+            let name = self.vars[id].name.clone();
+            let borrowed_at = self.loans[loan_id].span;
+            let diagnostic = Diagnostic::error(
+                Some("E0505"),
+                format!("cannot move out of `{name}` because it is borrowed"),
+                ident.span,
+                &format!("move out of `{name}` occurs here"),
+            )
+            .with_label(borrowed_at, format!("borrow of `{name}` occurs here"));
+            self.error(Checker::with_later_use(diagnostic, later, "borrow later used here"));
+        }
+        let var = &mut self.vars[id];
+        var.state = State::Moved(ident.span);
+        let loans = std::mem::take(&mut var.loans);
+        self.temps.extend(&loans);
+        Value { ty, loans }
+    }
+
+    /// Reading `id` (as `print` does) conflicts with a live `&mut` loan on it.
+    fn check_not_mutably_borrowed(&mut self, id: VarId, span: Span) {
+        let conflict = self.live_loans_on(id, span.start).into_iter().find(|&(loan_id, _)| self.loans[loan_id].mutable);
+        if let Some((loan_id, later)) = conflict {
+            let name = self.vars[id].name.clone();
+            let diagnostic = Diagnostic::error(
+                Some("E0502"),
+                format!("cannot borrow `{name}` as immutable because it is also borrowed as mutable"),
+                span,
+                "immutable borrow occurs here",
+            )
+            .with_label(self.loans[loan_id].span, "mutable borrow occurs here");
+            self.error(Checker::with_later_use(diagnostic, later, "mutable borrow later used here"));
+        }
+    }
+
+    fn borrow(&mut self, ident: &Ident, mutable: bool, span: Span) -> Value {
+        let Some(id) = self.lookup_or_report(ident) else {
+            return Value::unknown();
+        };
+        self.record_use(id, ident.span);
+        let name = self.vars[id].name.clone();
+        if !self.check_initialized(id, span, "borrow") {
+            return Value::unknown();
+        }
+        if mutable && !self.vars[id].mutable {
+            self.error(
+                Diagnostic::error(
+                    Some("E0596"),
+                    format!("cannot borrow `{name}` as mutable, as it is not declared as mutable"),
+                    span,
+                    "cannot borrow as mutable",
+                )
+                .with_note(format!("help: consider changing this to be mutable: `mut {name}`")),
+            );
+        }
+
+        let conflict =
+            self.live_loans_on(id, span.start).into_iter().find(|&(loan_id, _)| mutable || self.loans[loan_id].mutable);
+        if let Some((loan_id, later)) = conflict {
+            let first = &self.loans[loan_id];
+            let diagnostic = match (mutable, first.mutable) {
+                (true, true) => Diagnostic::error(
+                    Some("E0499"),
+                    format!("cannot borrow `{name}` as mutable more than once at a time"),
+                    span,
+                    "second mutable borrow occurs here",
+                )
+                .with_label(first.span, "first mutable borrow occurs here"),
+                (true, false) => Diagnostic::error(
+                    Some("E0502"),
+                    format!("cannot borrow `{name}` as mutable because it is also borrowed as immutable"),
+                    span,
+                    "mutable borrow occurs here",
+                )
+                .with_label(first.span, "immutable borrow occurs here"),
+                _ => Diagnostic::error(
+                    Some("E0502"),
+                    format!("cannot borrow `{name}` as immutable because it is also borrowed as mutable"),
+                    span,
+                    "immutable borrow occurs here",
+                )
+                .with_label(first.span, "mutable borrow occurs here"),
+            };
+            let later_text =
+                if first.mutable { "first borrow later used here" } else { "immutable borrow later used here" };
+            self.error(Checker::with_later_use(diagnostic, later, later_text));
+        }
+
+        let loan_id = self.loans.len();
+        self.loans.push(Loan { borrowed: id, mutable, span });
+        self.temps.push(loan_id);
+        let mut loans = vec![loan_id];
+        loans.extend(self.vars[id].loans.iter().copied());
+        let ty = self.vars[id].ty.clone().map(|inner| {
+            if mutable {
+                Type::RefMut(Box::new(inner))
+            } else {
+                Type::Ref(Box::new(inner))
+            }
+        });
+        Value { ty, loans }
+    }
+
+    /// `*x`. Only `print` may read a non-Copy value through a reference,
+    /// since it borrows rather than moves (`borrowing` set).
+    fn deref(&mut self, ident: &Ident, span: Span, borrowing: bool) -> Value {
+        let Some(id) = self.lookup_or_report(ident) else {
+            return Value::unknown();
+        };
+        self.record_use(id, ident.span);
+        if !self.check_initialized(id, ident.span, "use") {
+            return Value::unknown();
+        }
+        let name = self.vars[id].name.clone();
+        match self.vars[id].ty.clone() {
+            Some(Type::Ref(inner) | Type::RefMut(inner)) => {
+                if !borrowing && !inner.is_copy() {
+                    let behind = match self.vars[id].ty {
+                        Some(Type::RefMut(_)) => "mutable",
+                        _ => "shared",
+                    };
+                    self.error(Diagnostic::error(
+                        Some("E0507"),
+                        format!("cannot move out of `*{name}` which is behind a {behind} reference"),
+                        span,
+                        &format!("move occurs because `*{name}` has type `{inner}`, which does not implement the `Copy` trait"),
+                    ));
+                }
+                Value::of(*inner)
+            }
+            Some(ty) => {
+                self.error(Diagnostic::error(Some("E0614"), format!("type `{ty}` cannot be dereferenced"), span, ""));
+                Value::unknown()
+            }
+            None => Value::unknown(),
+        }
+    }
+
+    fn call(&mut self, name: &Ident, args: &[Expr], span: Span) -> Value {
+        match name.name.as_str() {
+            "print" => {
+                for arg in args {
+                    match arg {
+                        Expr::Var(ident) => {
+                            self.use_var(ident, false);
+                        }
+                        Expr::Deref { var, span } => {
+                            self.deref(var, *span, true);
+                        }
+                        other => {
+                            self.expr(other);
+                        }
+                    }
+                }
+                return Value::of(Type::Unit);
+            }
+            "drop" => {
+                if args.len() != 1 {
+                    self.error(Diagnostic::error(
+                        Some("E0061"),
+                        format!("this function takes 1 argument but {} arguments were supplied", args.len()),
+                        span,
+                        "",
+                    ));
+                }
+                for arg in args {
+                    self.expr(arg);
+                }
+                return Value::of(Type::Unit);
+            }
+            _ => {}
+        }
+
+        let Some(signature) = self.signatures.get(&name.name) else {
+            self.error(Diagnostic::error(
+                Some("E0425"),
+                format!("cannot find function `{}` in this scope", name.name),
+                name.span,
+                "not found in this scope",
+            ));
+            for arg in args {
+                self.expr(arg);
+            }
+            return Value::unknown();
+        };
+        if signature.params.len() != args.len() {
+            let plural = |n: usize| if n == 1 { "" } else { "s" };
+            let (expected, found) = (signature.params.len(), args.len());
+            self.error(Diagnostic::error(
+                Some("E0061"),
+                format!(
+                    "this function takes {expected} argument{} but {found} argument{} supplied",
+                    plural(expected),
+                    if found == 1 { " was" } else { "s were" }
+                ),
+                span,
+                "",
+            ));
+        }
+
+        let mut returned_loans = Vec::new();
+        for (arg, param) in args.iter().zip(signature.params.iter()) {
+            // Passing a `&mut` variable to a `&mut` parameter reborrows it
+            // instead of moving it, so it can be used again afterwards.
+            let reborrows = matches!(param, Type::RefMut(_))
+                && matches!(arg, Expr::Var(ident) if self.lookup(&ident.name)
+                    .is_some_and(|id| matches!(self.vars[id].ty, Some(Type::RefMut(_)))));
+            let value = match arg {
+                Expr::Var(ident) if reborrows => self.use_var(ident, false),
+                _ => self.expr(arg),
+            };
+            if let Some(found) = &value.ty {
+                if found != param {
+                    let label = format!("expected `{param}`, found `{found}`");
+                    self.error(Diagnostic::error(Some("E0308"), "mismatched types", arg.span(), &label));
+                }
+            }
+            if param.is_reference() {
+                returned_loans.extend(value.loans);
+            }
+        }
+        for arg in args.iter().skip(signature.params.len()) {
+            self.expr(arg);
+        }
+
+        let ret = signature.ret.clone();
+        let loans = if ret.is_reference() { returned_loans } else { Vec::new() };
+        self.temps.extend(&loans);
+        Value { ty: Some(ret), loans }
+    }
+}
+
+/// rustc's hint for a returned reference whose lifetime elision can't pick.
+fn elision_help(function: &Function, input_lifetimes: usize) -> String {
+    let help = "help: this function's return type contains a borrowed value";
+    let borrowed: Vec<String> = function
+        .params
+        .iter()
+        .filter(|(_, ty)| ty.lifetimes() > 0)
+        .map(|(param, _)| format!("`{}`", param.name))
+        .collect();
+    match borrowed.as_slice() {
+        [] => format!("{help}, but there is no value for it to be borrowed from"),
+        [only] => format!(
+            "{help}, but the signature does not say which one of {only}'s {input_lifetimes} lifetimes it is borrowed from"
+        ),
+        [init @ .., last] => format!(
+            "{help}, but the signature does not say whether it is borrowed from {} or {last}",
+            init.join(", ")
+        ),
+    }
+}
@@ -0,0 +1,71 @@
+// Tokens of the toy language. Keywords (`fn`, `let`, `mut`) come out as
+// identifiers; the parser tells them apart.
+
+use super::{Diagnostic, Span};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident(String),
+    Int(i64),
+    Str(String),
+    /// One of `( ) { } ; , = & * : -> ::`.
+    Punct(&'static str),
+    Eof,
+}
+
+#[derive(Clone, Debug)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+const PUNCTS: &[&str] = &["->", "::", "(", ")", "{", "}", ";", ",", "=", "&", "*", ":"];
+
+pub fn tokenize(source: &str) -> Result<Vec<Token>, Diagnostic> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            i += 1;
+        } else if source[i..].starts_with("//") {
+            i = source[i..].find('\n').map_or(bytes.len(), |n| i + n);
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            let start = i;
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            tokens.push(Token { kind: TokenKind::Ident(source[start..i].to_string()), span: Span::new(start, i) });
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            let span = Span::new(start, i);
+            let value = source[start..i]
+                .parse()
+                .map_err(|_| Diagnostic::error(None, "integer literal is too large", span, ""))?;
+            tokens.push(Token { kind: TokenKind::Int(value), span });
+        } else if c == b'"' {
+            let start = i;
+            let end = source[i + 1..].find('"').map(|n| i + 1 + n).ok_or_else(|| {
+                Diagnostic::error(None, "unterminated double quote string", Span::new(start, bytes.len()), "")
+            })?;
+            tokens.push(Token {
+                kind: TokenKind::Str(source[start + 1..end].to_string()),
+                span: Span::new(start, end + 1),
+            });
+            i = end + 1;
+        } else if let Some(punct) = PUNCTS.iter().find(|p| source[i..].starts_with(**p)) {
+            tokens.push(Token { kind: TokenKind::Punct(punct), span: Span::new(i, i + punct.len()) });
+            i += punct.len();
+        } else {
+            let ch = source[i..].chars().next().unwrap();
+            let span = Span::new(i, i + ch.len_utf8());
+            return Err(Diagnostic::error(None, format!("unknown start of token: {ch}"), span, ""));
+        }
+    }
+    tokens.push(Token { kind: TokenKind::Eof, span: Span::new(bytes.len(), bytes.len()) });
+    Ok(tokens)
+}
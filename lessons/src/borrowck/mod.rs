@@ -0,0 +1,180 @@
+// Borrow-checker simulator
+// The ownership and borrowing lessons state the rules; this module enforces
+// them on a tiny Rust-like language so they can be tried out without fighting
+// the real compiler. A program is a list of functions:
+//
+//     fn main() {
+//         let mut s = String::from("hello");
+//         let r1 = &mut s;
+//         let r2 = &mut s;
+//         print(r1, r2);
+//     }
+//
+// The language has
+//   - types `i32`, `String`, `&T` and `&mut T`; i32 and `&T` are Copy,
+//     String and `&mut T` move,
+//   - `let x = e;`, `let mut x = e;`, `let x;`, `x = e;` and `*x = e;`,
+//   - expressions: integers, `String::from("...")`, variables, `&x`,
+//     `&mut x`, `*x`, calls and `{ ... }` blocks with an optional tail,
+//   - functions `fn name(p: Type, ...) -> Type { ... }`, plus the built-ins
+//     `print(...)` (borrows its arguments, like `println!`) and `drop(x)`.
+//
+// Borrows end after the reference's last use, as with non-lexical lifetimes.
+// Violations are reported the way rustc would, with the same error codes:
+// use after move (E0382), two `&mut` at once (E0499), `&mut` while shared
+// (E0502), dangling references (E0597, E0515, E0106) and a few more.
+
+mod checker;
+mod lexer;
+mod parser;
+
+use std::fmt::Write as _;
+
+pub use parser::MAX_NESTING;
+
+/// A byte range in the checked source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+
+    /// From the start of `self` to the end of `other`.
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.start, other.end)
+    }
+
+    /// 1-based line and column of the start of the span.
+    pub fn line_col(self, source: &str) -> (usize, usize) {
+        let before = &source[..self.start.min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let col = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+        (line, col)
+    }
+}
+
+/// A span with a short explanation underneath it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub text: String,
+    /// The primary label is underlined with `^`, the others with `-`.
+    pub primary: bool,
+}
+
+/// One error, in the shape rustc prints it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// rustc's error code, e.g. `E0382`. Syntax errors have none.
+    pub code: Option<&'static str>,
+    pub message: String,
+    pub labels: Vec<Label>,
+    /// Lines printed after the source, e.g. `help: consider ...`.
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(code: Option<&'static str>, message: impl Into<String>, span: Span, label: &str) -> Self {
+        Diagnostic {
+            code,
+            message: message.into(),
+            labels: vec![Label { span, text: label.to_string(), primary: true }],
+            notes: Vec::new(),
+        }
+    }
+
+    /// Adds a secondary label.
+    pub fn with_label(mut self, span: Span, text: impl Into<String>) -> Self {
+        self.labels.push(Label { span, text: text.into(), primary: false });
+        self
+    }
+
+    /// Adds a `note: ...` or `help: ...` line.
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn primary_span(&self) -> Span {
+        self.labels.iter().find(|l| l.primary).map_or(Span::new(0, 0), |l| l.span)
+    }
+
+    /// Renders the error with the source lines it points at, like rustc.
+    pub fn render(&self, source: &str) -> String {
+        let mut out = String::new();
+        match self.code {
+            Some(code) => writeln!(out, "error[{code}]: {}", self.message),
+            None => writeln!(out, "error: {}", self.message),
+        }
+        .unwrap();
+
+        let lines: Vec<&str> = source.split('\n').collect();
+        let mut labels: Vec<(usize, usize, &Label)> = self
+            .labels
+            .iter()
+            .map(|label| {
+                let (line, col) = label.span.line_col(source);
+                (line, col, label)
+            })
+            .collect();
+        labels.sort_by_key(|&(line, col, _)| (line, col));
+
+        let width = labels.iter().map(|(line, ..)| line.to_string().len()).max().unwrap_or(1);
+        let (line, col) = self.primary_span().line_col(source);
+        writeln!(out, "{:width$}--> {line}:{col}", "").unwrap();
+        writeln!(out, "{:width$} |", "").unwrap();
+
+        let mut previous_line = None;
+        for (line, col, label) in labels {
+            if previous_line != Some(line) {
+                if previous_line.is_some_and(|previous| line > previous + 1) {
+                    writeln!(out, "...").unwrap();
+                }
+                let text = lines.get(line - 1).copied().unwrap_or("");
+                writeln!(out, "{line:>width$} | {text}").unwrap();
+                previous_line = Some(line);
+            }
+            let text_here = &source[label.span.start.min(source.len())..label.span.end.min(source.len())];
+            let len = text_here.split('\n').next().unwrap_or("").chars().count().max(1);
+            let mark = if label.primary { "^" } else { "-" };
+            let underline = mark.repeat(len);
+            let pad = " ".repeat(col - 1);
+            let line = format!("{:width$} | {pad}{underline} {}", "", label.text);
+            writeln!(out, "{}", line.trim_end()).unwrap();
+        }
+        if !self.notes.is_empty() {
+            writeln!(out, "{:width$} |", "").unwrap();
+        }
+        for note in &self.notes {
+            writeln!(out, "{:width$} = {note}", "").unwrap();
+        }
+        out.trim_end().to_string() + "\n"
+    }
+}
+
+/// Checks `source` and returns every violation found, in source order.
+/// An empty list means the program would pass the borrow checker.
+pub fn check(source: &str) -> Vec<Diagnostic> {
+    let program = match lexer::tokenize(source).and_then(|tokens| parser::parse(&tokens)) {
+        Ok(program) => program,
+        Err(syntax_error) => return vec![syntax_error],
+    };
+    let mut diagnostics = checker::check(&program);
+    diagnostics.sort_by_key(Diagnostic::primary_span);
+    diagnostics
+}
+
+/// `check` followed by `render` for every error, or `None` if there are none.
+pub fn report(source: &str) -> Option<String> {
+    let diagnostics = check(source);
+    if diagnostics.is_empty() {
+        return None;
+    }
+    let rendered: Vec<String> = diagnostics.iter().map(|d| d.render(source)).collect();
+    Some(rendered.join("\n"))
+}
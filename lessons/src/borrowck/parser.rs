@@ -0,0 +1,417 @@
+// Syntax tree and recursive-descent parser for the toy language.
+
+use std::fmt;
+
+use super::lexer::{Token, TokenKind};
+use super::{Diagnostic, Span};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    I32,
+    String,
+    Ref(Box<Type>),
+    RefMut(Box<Type>),
+    Unit,
+}
+
+impl Type {
+    /// Copy types are duplicated on use instead of moved.
+    pub fn is_copy(&self) -> bool {
+        matches!(self, Type::I32 | Type::Ref(_) | Type::Unit)
+    }
+
+    pub fn is_reference(&self) -> bool {
+        matches!(self, Type::Ref(_) | Type::RefMut(_))
+    }
+
+    /// How many elided lifetimes the type has: one per `&` or `&mut`.
+    pub fn lifetimes(&self) -> usize {
+        match self {
+            Type::Ref(inner) | Type::RefMut(inner) => 1 + inner.lifetimes(),
+            Type::I32 | Type::String | Type::Unit => 0,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::I32 => f.write_str("i32"),
+            Type::String => f.write_str("String"),
+            Type::Ref(inner) => write!(f, "&{inner}"),
+            Type::RefMut(inner) => write!(f, "&mut {inner}"),
+            Type::Unit => f.write_str("()"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Ident {
+    pub name: String,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Program {
+    pub functions: Vec<Function>,
+}
+
+#[derive(Debug)]
+pub struct Function {
+    pub name: Ident,
+    pub params: Vec<(Ident, Type)>,
+    pub ret: Type,
+    /// The return type, for errors about it; the `)` when there is none.
+    pub ret_span: Span,
+    pub body: Block,
+}
+
+#[derive(Debug)]
+pub struct Block {
+    pub stmts: Vec<Stmt>,
+    pub tail: Option<Box<Expr>>,
+    /// The closing `}`, where the block's locals are dropped.
+    pub close: Span,
+}
+
+#[derive(Debug)]
+pub enum Stmt {
+    Let {
+        name: Ident,
+        mutable: bool,
+        init: Option<Expr>,
+    },
+    /// `x = value;`, or `*x = value;` when `deref` is set.
+    Assign {
+        target: Ident,
+        deref: bool,
+        value: Expr,
+    },
+    Expr(Expr),
+}
+
+#[derive(Debug)]
+pub enum Expr {
+    Int(Span),
+    /// `String::from("...")`
+    StringFrom(Span),
+    Var(Ident),
+    Borrow {
+        mutable: bool,
+        var: Ident,
+        span: Span,
+    },
+    Deref {
+        var: Ident,
+        span: Span,
+    },
+    Call {
+        name: Ident,
+        args: Vec<Expr>,
+        span: Span,
+    },
+    Block(Block),
+}
+
+impl Expr {
+    pub fn span(&self) -> Span {
+        match self {
+            Expr::Int(span) | Expr::StringFrom(span) => *span,
+            Expr::Var(ident) => ident.span,
+            Expr::Borrow { span, .. } | Expr::Deref { span, .. } | Expr::Call { span, .. } => *span,
+            Expr::Block(block) => block.close,
+        }
+    }
+}
+
+/// How deeply blocks, calls and reference types may nest. The parser and the
+/// checker both recurse once per level, so without a limit a long enough
+/// `{{{{...` would overflow the stack instead of getting an error.
+pub const MAX_NESTING: usize = 64;
+
+pub fn parse(tokens: &[Token]) -> Result<Program, Diagnostic> {
+    let mut parser = Parser { tokens, pos: 0, depth: 0 };
+    let mut functions = Vec::new();
+    while parser.peek() != &TokenKind::Eof {
+        functions.push(parser.function()?);
+    }
+    Ok(Program { functions })
+}
+
+struct Parser<'t> {
+    tokens: &'t [Token],
+    pos: usize,
+    /// How many `expr`, `block` and `ty` calls are in progress.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> &TokenKind {
+        &self.tokens[self.pos].kind
+    }
+
+    fn peek_at(&self, offset: usize) -> &TokenKind {
+        let last = self.tokens.len() - 1;
+        &self.tokens[(self.pos + offset).min(last)].kind
+    }
+
+    fn span(&self) -> Span {
+        self.tokens[self.pos].span
+    }
+
+    fn advance(&mut self) -> &Token {
+        let token = &self.tokens[self.pos];
+        if token.kind != TokenKind::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn is_punct(&self, punct: &str) -> bool {
+        matches!(self.peek(), TokenKind::Punct(p) if *p == punct)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), TokenKind::Ident(name) if name == keyword)
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        let found = self.is_punct(punct);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn unexpected(&self, expected: &str) -> Diagnostic {
+        let found = match self.peek() {
+            TokenKind::Ident(name) => format!("`{name}`"),
+            TokenKind::Int(value) => format!("`{value}`"),
+            TokenKind::Str(text) => format!("`\"{text}\"`"),
+            TokenKind::Punct(p) => format!("`{p}`"),
+            TokenKind::Eof => "end of input".to_string(),
+        };
+        Diagnostic::error(None, format!("expected {expected}, found {found}"), self.span(), "")
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> Result<Span, Diagnostic> {
+        let span = self.span();
+        if self.eat_punct(punct) {
+            Ok(span)
+        } else {
+            Err(self.unexpected(&format!("`{punct}`")))
+        }
+    }
+
+    /// Runs `parse` one nesting level deeper, or fails past `MAX_NESTING`.
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, Diagnostic>) -> Result<T, Diagnostic> {
+        if self.depth >= MAX_NESTING {
+            return Err(Diagnostic::error(None, "expression nested too deeply", self.span(), "")
+                .with_note(format!("note: at most {MAX_NESTING} levels of blocks, calls and `&` are allowed")));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn ident(&mut self) -> Result<Ident, Diagnostic> {
+        match self.peek().clone() {
+            TokenKind::Ident(name) if !matches!(name.as_str(), "fn" | "let" | "mut") => {
+                let span = self.advance().span;
+                Ok(Ident { name, span })
+            }
+            _ => Err(self.unexpected("an identifier")),
+        }
+    }
+
+    fn function(&mut self) -> Result<Function, Diagnostic> {
+        if !self.eat_keyword("fn") {
+            return Err(self.unexpected("`fn`"));
+        }
+        let name = self.ident()?;
+        self.expect_punct("(")?;
+        let mut params = Vec::new();
+        while !self.is_punct(")") {
+            let param = self.ident()?;
+            self.expect_punct(":")?;
+            params.push((param, self.ty()?));
+            if !self.eat_punct(",") {
+                break;
+            }
+        }
+        let close_paren = self.expect_punct(")")?;
+        let (ret, ret_span) = if self.eat_punct("->") {
+            let start = self.span().start;
+            let ty = self.ty()?;
+            let end = self.tokens[self.pos - 1].span.end;
+            (ty, Span::new(start, end))
+        } else {
+            (Type::Unit, close_paren)
+        };
+        let body = self.block()?;
+        Ok(Function { name, params, ret, ret_span, body })
+    }
+
+    fn ty(&mut self) -> Result<Type, Diagnostic> {
+        self.nested(Self::ty_unnested)
+    }
+
+    fn ty_unnested(&mut self) -> Result<Type, Diagnostic> {
+        if self.eat_punct("&") {
+            let mutable = self.eat_keyword("mut");
+            let inner = Box::new(self.ty()?);
+            return Ok(if mutable { Type::RefMut(inner) } else { Type::Ref(inner) });
+        }
+        if self.is_punct("(") && matches!(self.peek_at(1), TokenKind::Punct(")")) {
+            self.advance();
+            self.advance();
+            return Ok(Type::Unit);
+        }
+        match self.peek() {
+            TokenKind::Ident(name) if name == "i32" => {
+                self.advance();
+                Ok(Type::I32)
+            }
+            TokenKind::Ident(name) if name == "String" => {
+                self.advance();
+                Ok(Type::String)
+            }
+            _ => Err(self.unexpected("a type (`i32`, `String`, `&T` or `&mut T`)")),
+        }
+    }
+
+    fn block(&mut self) -> Result<Block, Diagnostic> {
+        self.nested(Self::block_unnested)
+    }
+
+    fn block_unnested(&mut self) -> Result<Block, Diagnostic> {
+        self.expect_punct("{")?;
+        let mut stmts = Vec::new();
+        let mut tail = None;
+        loop {
+            if self.is_punct("}") {
+                break;
+            }
+            if self.is_keyword("let") {
+                stmts.push(self.let_stmt()?);
+                continue;
+            }
+            if let Some(assign) = self.assign_stmt()? {
+                stmts.push(assign);
+                continue;
+            }
+            let expr = self.expr()?;
+            if self.eat_punct(";") {
+                stmts.push(Stmt::Expr(expr));
+            } else if self.is_punct("}") {
+                tail = Some(Box::new(expr));
+            } else if matches!(expr, Expr::Block(_)) {
+                // A block statement doesn't need a `;`.
+                stmts.push(Stmt::Expr(expr));
+            } else {
+                return Err(self.unexpected("`;` or `}`"));
+            }
+        }
+        let close = self.expect_punct("}")?;
+        Ok(Block { stmts, tail, close })
+    }
+
+    fn let_stmt(&mut self) -> Result<Stmt, Diagnostic> {
+        self.advance(); // let
+        let mutable = self.eat_keyword("mut");
+        let name = self.ident()?;
+        let init = if self.eat_punct("=") { Some(self.expr()?) } else { None };
+        self.expect_punct(";")?;
+        Ok(Stmt::Let { name, mutable, init })
+    }
+
+    /// `x = e;` or `*x = e;`; anything else is left for `expr`.
+    fn assign_stmt(&mut self) -> Result<Option<Stmt>, Diagnostic> {
+        let deref = self.is_punct("*");
+        let (ident_at, eq_at) = if deref { (1, 2) } else { (0, 1) };
+        let is_assign = matches!(self.peek_at(ident_at), TokenKind::Ident(_))
+            && matches!(self.peek_at(eq_at), TokenKind::Punct("="));
+        if !is_assign {
+            return Ok(None);
+        }
+        if deref {
+            self.advance();
+        }
+        let target = self.ident()?;
+        self.expect_punct("=")?;
+        let value = self.expr()?;
+        self.expect_punct(";")?;
+        Ok(Some(Stmt::Assign { target, deref, value }))
+    }
+
+    fn expr(&mut self) -> Result<Expr, Diagnostic> {
+        self.nested(Self::expr_unnested)
+    }
+
+    fn expr_unnested(&mut self) -> Result<Expr, Diagnostic> {
+        let start = self.span();
+        if self.eat_punct("&") {
+            let mutable = self.eat_keyword("mut");
+            let var = self.ident()?;
+            let span = start.to(var.span);
+            return Ok(Expr::Borrow { mutable, var, span });
+        }
+        if self.eat_punct("*") {
+            let var = self.ident()?;
+            let span = start.to(var.span);
+            return Ok(Expr::Deref { var, span });
+        }
+        if self.is_punct("{") {
+            return Ok(Expr::Block(self.block()?));
+        }
+        match self.peek().clone() {
+            TokenKind::Int(_) => {
+                self.advance();
+                Ok(Expr::Int(start))
+            }
+            TokenKind::Ident(name) if name == "String" => {
+                self.advance();
+                self.expect_punct("::")?;
+                match self.peek().clone() {
+                    TokenKind::Ident(from) if from == "from" => {
+                        self.advance();
+                    }
+                    _ => return Err(self.unexpected("`from`")),
+                }
+                self.expect_punct("(")?;
+                if !matches!(self.peek(), TokenKind::Str(_)) {
+                    return Err(self.unexpected("a string literal"));
+                }
+                self.advance();
+                let end = self.expect_punct(")")?;
+                Ok(Expr::StringFrom(start.to(end)))
+            }
+            TokenKind::Ident(_) => {
+                let name = self.ident()?;
+                if !self.eat_punct("(") {
+                    return Ok(Expr::Var(name));
+                }
+                let mut args = Vec::new();
+                while !self.is_punct(")") {
+                    args.push(self.expr()?);
+                    if !self.eat_punct(",") {
+                        break;
+                    }
+                }
+                let end = self.expect_punct(")")?;
+                let span = name.span.to(end);
+                Ok(Expr::Call { name, args, span })
+            }
+            _ => Err(self.unexpected("an expression")),
+        }
+    }
+}
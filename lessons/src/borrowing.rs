@@ -0,0 +1,31 @@
+// References and Borrowing
+// A reference lets a function use a value without taking ownership of it.
+
+/// Returns the length of `s` in bytes without taking ownership of it.
+/// Compare `ownership::calculate_length`, which takes the String and has to
+/// give it back in a tuple.
+#[allow(clippy::ptr_arg)] // the lesson is about `&String`, not `&str`
+pub fn calculate_length(s: &String) -> usize {
+    // s is a reference to a String
+    s.len()
+} // Here, s goes out of scope. But because it does not have ownership of what
+  // it refers to, it is not dropped.
+
+/// The same length for anything that can be viewed as a `&str`: `&String`,
+/// string literals, `String`, `Box<str>`...
+pub fn calculate_length_as_ref<S: AsRef<str>>(s: S) -> usize {
+    s.as_ref().len()
+}
+
+/// Appends `", world"` to the borrowed String. Needs `&mut`: with a plain
+/// `&String` the `push_str` call is rejected (E0596).
+pub fn change(some_string: &mut String) {
+    some_string.push_str(", world");
+}
+
+/// Replaces the borrowed String through the reference. `*some_string` is the
+/// caller's String itself, so the old value is dropped and the caller sees
+/// the new one.
+pub fn replace(some_string: &mut String, with: &str) {
+    *some_string = String::from(with);
+}
@@ -0,0 +1,348 @@
+// Inspecting chars
+// A `char` is four bytes and holds one Unicode Scalar Value, but a String
+// stores its text as UTF-8, where a char takes one to four bytes. That's why
+// `String::len()` (and so `calculate_length`) counts bytes, not characters:
+// "ℤ😻z" is 3 chars but 8 bytes. `CharInfo` spells out how one char is
+// encoded, and `StrReport` does it for every char of a string.
+//
+// The general category is the major class only (letter, number, ...), worked
+// out from std's `char` predicates plus a few ranges of marks, format
+// characters, punctuation and symbols: std doesn't ship the full Unicode
+// Character Database, so anything outside those is reported as unknown.
+// `is_alphabetic` also covers some marks, numbers and symbols, so those are
+// ruled out first, and a caseless alphabetic char from a script with vowel
+// signs is reported as "letter or mark" rather than guessed at.
+
+use std::fmt;
+
+/// The general category of a char, as far as std can tell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Category {
+    UppercaseLetter,
+    LowercaseLetter,
+    /// Letters without case: CJK ideographs, modifier letters, ...
+    OtherLetter,
+    /// Alphabetic without case, outside the scripts known to be all letters:
+    /// a letter such as ক, or a vowel sign such as ि that std counts as
+    /// alphabetic too. std can't tell the two apart.
+    LetterLike,
+    Number,
+    Whitespace,
+    Control,
+    /// Combining marks, such as the accent in "e\u{301}".
+    Mark,
+    /// Invisible formatting characters: zero-width space, soft hyphen, ...
+    Format,
+    Punctuation,
+    Symbol,
+    PrivateUse,
+    /// Not in any of the ranges this module knows.
+    Unknown,
+}
+
+impl Category {
+    pub fn of(c: char) -> Self {
+        if c.is_control() {
+            Category::Control
+        } else if matches!(
+            c,
+            '\u{300}'..='\u{36F}'
+                | '\u{1AB0}'..='\u{1AFF}'
+                | '\u{1DC0}'..='\u{1DFF}'
+                | '\u{20D0}'..='\u{20FF}'
+                | '\u{FE00}'..='\u{FE0F}'
+                | '\u{FE20}'..='\u{FE2F}'
+        ) {
+            Category::Mark
+        } else if matches!(
+            c,
+            '\u{AD}'
+                | '\u{600}'..='\u{605}'
+                | '\u{61C}'
+                | '\u{6DD}'
+                | '\u{70F}'
+                | '\u{180E}'
+                | '\u{200B}'..='\u{200F}'
+                | '\u{202A}'..='\u{202E}'
+                | '\u{2060}'..='\u{2064}'
+                | '\u{2066}'..='\u{206F}'
+                | '\u{FEFF}'
+                | '\u{FFF9}'..='\u{FFFB}'
+                | '\u{E0001}'
+                | '\u{E0020}'..='\u{E007F}'
+        ) {
+            Category::Format
+        } else if c.is_whitespace() {
+            Category::Whitespace
+        } else if c.is_numeric() {
+            // Before the letter test: Roman numerals like Ⅻ are alphabetic too.
+            Category::Number
+        } else if matches!(c, '\u{2190}'..='\u{2BFF}' | '\u{3200}'..='\u{33FF}' | '\u{1F000}'..='\u{1FAFF}') {
+            // Arrows, maths, box drawing, dingbats, enclosed CJK and emoji. Also
+            // before the letter test, since circled letters like Ⓐ count as
+            // alphabetic and uppercase.
+            Category::Symbol
+        } else if c.is_alphabetic() {
+            if is_modifier_letter(c) {
+                // Other_Lowercase, so `is_lowercase` is true, but they're Lm/Lo.
+                Category::OtherLetter
+            } else if c.is_uppercase() {
+                Category::UppercaseLetter
+            } else if c.is_lowercase() {
+                Category::LowercaseLetter
+            } else if is_caseless_letter(c) {
+                Category::OtherLetter
+            } else {
+                Category::LetterLike
+            }
+        } else if c.is_ascii() {
+            // What's left of ASCII is punctuation and symbols: $ + < = > ^ ` | ~
+            if "$+<=>^`|~".contains(c) {
+                Category::Symbol
+            } else {
+                Category::Punctuation
+            }
+        } else if matches!(c, '\u{E000}'..='\u{F8FF}' | '\u{F0000}'..='\u{FFFFD}' | '\u{100000}'..='\u{10FFFD}') {
+            // The last two code points of each plane are noncharacters, not private use.
+            Category::PrivateUse
+        } else if matches!(
+            c,
+            '\u{A1}' | '\u{A7}' | '\u{AB}' | '\u{B6}' | '\u{B7}' | '\u{BB}' | '\u{BF}'
+                | '\u{2010}'..='\u{2027}'
+                | '\u{2030}'..='\u{205E}'
+                | '\u{3001}'..='\u{3003}'
+                | '\u{3008}'..='\u{3011}'
+        ) {
+            Category::Punctuation
+        } else if matches!(
+            c,
+            '\u{A2}'..='\u{A9}'
+                | '\u{AC}'
+                | '\u{AE}'..='\u{B1}'
+                | '\u{B4}'
+                | '\u{B8}'
+                | '\u{D7}'
+                | '\u{F7}'
+                | '\u{20A0}'..='\u{20CF}'
+                | '\u{2100}'..='\u{214F}'
+        ) {
+            // Currency and letterlike symbols; letters such as ℤ among the
+            // letterlike ones were caught above.
+            Category::Symbol
+        } else {
+            Category::Unknown
+        }
+    }
+
+    /// The Unicode abbreviation: two letters where std can tell that much
+    /// (`Lu`, `Ll`, `Cc`, `Cf`, `Co`), the major class otherwise, and `?` for
+    /// unknown.
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            Category::UppercaseLetter => "Lu",
+            Category::LowercaseLetter => "Ll",
+            Category::OtherLetter => "L",
+            Category::LetterLike => "L/M",
+            Category::Number => "N",
+            Category::Whitespace => "Z",
+            Category::Control => "Cc",
+            Category::Mark => "M",
+            Category::Format => "Cf",
+            Category::Punctuation => "P",
+            Category::Symbol => "S",
+            Category::PrivateUse => "Co",
+            Category::Unknown => "?",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::UppercaseLetter => "uppercase letter",
+            Category::LowercaseLetter => "lowercase letter",
+            Category::OtherLetter => "letter",
+            Category::LetterLike => "letter or mark",
+            Category::Number => "number",
+            Category::Whitespace => "separator",
+            Category::Control => "control",
+            Category::Mark => "mark",
+            Category::Format => "format",
+            Category::Punctuation => "punctuation",
+            Category::Symbol => "symbol",
+            Category::PrivateUse => "private use",
+            Category::Unknown => "unknown",
+        }
+    }
+}
+
+/// Modifier letters (Lm) and the two ordinal indicators (Lo) that std
+/// counts as lowercase: ª, ʰ, ˠ, ⁿ, ...
+fn is_modifier_letter(c: char) -> bool {
+    matches!(
+        c,
+        '\u{AA}'
+            | '\u{BA}'
+            | '\u{2B0}'..='\u{2B8}'
+            | '\u{2C0}'..='\u{2C1}'
+            | '\u{2E0}'..='\u{2E4}'
+            | '\u{37A}'
+            | '\u{10FC}'
+            | '\u{1D2C}'..='\u{1D6A}'
+            | '\u{1D78}'
+            | '\u{1D9B}'..='\u{1DBF}'
+            | '\u{2071}'
+            | '\u{207F}'
+            | '\u{2090}'..='\u{209C}'
+            | '\u{2C7C}'..='\u{2C7D}'
+            | '\u{A69C}'..='\u{A69D}'
+            | '\u{A770}'
+            | '\u{A7F8}'..='\u{A7F9}'
+            | '\u{AB5C}'..='\u{AB5F}'
+            | '\u{AB69}'
+            | '\u{10780}'..='\u{107BA}'
+            | '\u{1E030}'..='\u{1E06D}'
+    )
+}
+
+/// Scripts whose alphabetic chars are all letters, with no combining marks
+/// mixed in: Hebrew and Arabic letters, Hangul, kana, CJK ideographs, Yi and
+/// Lisu.
+fn is_caseless_letter(c: char) -> bool {
+    matches!(
+        c,
+        '\u{5D0}'..='\u{5EA}'
+            | '\u{620}'..='\u{64A}'
+            | '\u{1100}'..='\u{11FF}'
+            | '\u{3041}'..='\u{3096}'
+            | '\u{30A1}'..='\u{30FA}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{A000}'..='\u{A48C}'
+            | '\u{A4D0}'..='\u{A4FD}'
+            | '\u{AC00}'..='\u{D7A3}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{323AF}'
+    )
+}
+
+/// Everything about how one char is stored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CharInfo {
+    pub ch: char,
+    pub code_point: u32,
+    pub utf8: Vec<u8>,
+    pub utf16: Vec<u16>,
+    pub category: Category,
+    pub is_ascii: bool,
+}
+
+impl CharInfo {
+    pub fn of(ch: char) -> Self {
+        let mut utf8 = [0; 4];
+        let mut utf16 = [0; 2];
+        CharInfo {
+            ch,
+            code_point: u32::from(ch),
+            utf8: ch.encode_utf8(&mut utf8).as_bytes().to_vec(),
+            utf16: ch.encode_utf16(&mut utf16).to_vec(),
+            category: Category::of(ch),
+            is_ascii: ch.is_ascii(),
+        }
+    }
+
+    /// `U+2124`: at least four hex digits, as Unicode writes them.
+    pub fn code_point_text(&self) -> String {
+        format!("U+{:04X}", self.code_point)
+    }
+
+    pub fn utf8_text(&self) -> String {
+        hex(self.utf8.iter().map(|b| format!("{b:02x}")))
+    }
+
+    pub fn utf16_text(&self) -> String {
+        hex(self.utf16.iter().map(|u| format!("{u:04x}")))
+    }
+}
+
+fn hex(units: impl Iterator<Item = String>) -> String {
+    units.collect::<Vec<_>>().join(" ")
+}
+
+impl fmt::Display for CharInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:?} {}", self.ch, self.code_point_text())?;
+        writeln!(f, "  UTF-8:    {} (len_utf8 = {})", self.utf8_text(), self.ch.len_utf8())?;
+        writeln!(f, "  UTF-16:   {} (len_utf16 = {})", self.utf16_text(), self.ch.len_utf16())?;
+        writeln!(f, "  category: {} ({})", self.category.abbreviation(), self.category.name())?;
+        writeln!(f, "  ASCII:    {}", if self.is_ascii { "yes" } else { "no" })?;
+        writeln!(f, "  as a char it still takes size_of::<char>() = {} bytes", std::mem::size_of::<char>())
+    }
+}
+
+/// Every char of a string with the byte offset it starts at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StrReport {
+    pub text: String,
+    pub chars: Vec<(usize, CharInfo)>,
+}
+
+impl StrReport {
+    pub fn of(text: &str) -> Self {
+        StrReport { text: text.to_string(), chars: text.char_indices().map(|(i, c)| (i, CharInfo::of(c))).collect() }
+    }
+
+    /// `String::len()`: bytes of UTF-8.
+    pub fn byte_len(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn char_count(&self) -> usize {
+        self.chars.len()
+    }
+
+    pub fn utf16_len(&self) -> usize {
+        self.chars.iter().map(|(_, info)| info.utf16.len()).sum()
+    }
+}
+
+impl fmt::Display for StrReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:?}: len() = {} bytes, chars().count() = {}, encode_utf16().count() = {}",
+            self.text,
+            self.byte_len(),
+            self.char_count(),
+            self.utf16_len()
+        )?;
+        let rows: Vec<[String; 6]> = self
+            .chars
+            .iter()
+            .map(|(offset, info)| {
+                [
+                    offset.to_string(),
+                    info.code_point_text(),
+                    info.utf8_text(),
+                    info.utf16_text(),
+                    info.category.abbreviation().to_string(),
+                    if info.is_ascii { "yes" } else { "no" }.to_string(),
+                ]
+            })
+            .collect();
+        let header = ["byte", "code point", "UTF-8", "UTF-16", "cat", "ASCII"];
+        let mut widths = header.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        let cells = |row: [&str; 6]| -> String {
+            row.iter().zip(widths).map(|(cell, w)| format!("{cell:w$}")).collect::<Vec<_>>().join("  ")
+        };
+        // The char itself goes last: terminals don't agree on how wide an emoji is.
+        writeln!(f, "  {}  char", cells(header))?;
+        for (row, (_, info)) in rows.iter().zip(&self.chars) {
+            writeln!(f, "  {}  {:?}", cells(row.each_ref().map(String::as_str)), info.ch)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+// Tuples and arrays
+// Indexing an array with `a[i]` checks the index at runtime and panics when
+// it's out of bounds. `get` does the same check but hands back an `Option`
+// instead, and patterns can take tuples, arrays and slices apart without
+// indexing at all, so there's nothing to get wrong.
+
+use std::fmt;
+use std::panic;
+
+/// An index past the end, reported with the same words as the panic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfBounds {
+    pub index: usize,
+    pub len: usize,
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "index out of bounds: the len is {} but the index is {}", self.len, self.index)
+    }
+}
+
+impl std::error::Error for OutOfBounds {}
+
+/// `a[index]` without the panic.
+pub fn element<T: Copy>(a: &[T], index: usize) -> Result<T, OutOfBounds> {
+    a.get(index).copied().ok_or(OutOfBounds { index, len: a.len() })
+}
+
+/// Runs `a[index]` for real and catches the panic if there is one, returning
+/// its message. The panic hook is left alone, so the usual
+/// `thread '...' panicked at` report still goes to stderr.
+pub fn index_catching_panic(a: &[i32], index: usize) -> Result<i32, String> {
+    panic::catch_unwind(|| a[index]).map_err(|payload| {
+        payload
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_else(|| "panicked".to_string())
+    })
+}
+
+/// The first and last elements, using a slice pattern. A single element is
+/// both.
+pub fn first_and_last<T>(s: &[T]) -> Option<(&T, &T)> {
+    match s {
+        [] => None,
+        [only] => Some((only, only)),
+        [first, .., last] => Some((first, last)),
+    }
+}
+
+/// Says what shape a slice has, one arm per pattern.
+pub fn shape(s: &[i32]) -> String {
+    match s {
+        [] => "empty".to_string(),
+        [x] => format!("one element: {x}"),
+        [x, y] => format!("a pair: {x} and {y}"),
+        [first, middle @ .., last] => format!("{first}, then {} more, then {last}", middle.len()),
+    }
+}
+
+/// Splits `[head, rest @ ..]`; `None` for an empty slice.
+pub fn head_tail<T>(s: &[T]) -> Option<(&T, &[T])> {
+    match s {
+        [head, rest @ ..] => Some((head, rest)),
+        [] => None,
+    }
+}
+
+/// Pulls every field out of a nested tuple/array value in one `let`.
+pub fn flatten(value: ((i32, f64), [u8; 3], &str)) -> (i32, f64, u8, u8, u8, &str) {
+    let ((x, y), [a, b, c], name) = value;
+    (x, y, a, b, c, name)
+}
+
+/// Sums the `.0` and `.1` fields of a list of points by destructuring each
+/// one in the `for` pattern.
+pub fn sum_points(points: &[(i32, i32)]) -> (i32, i32) {
+    let mut sum = (0, 0);
+    for &(x, y) in points {
+        sum.0 += x;
+        sum.1 += y;
+    }
+    sum
+}
+
+/// `[value; N]`: N copies. The element type must be `Copy` (or the value a
+/// constant), since one value is duplicated into every slot.
+pub fn filled<T: Copy, const N: usize>(value: T) -> [T; N] {
+    [value; N]
+}
@@ -0,0 +1,265 @@
+// Countdown
+// The control-flow lesson counts down to LIFTOFF twice, once with `while` and
+// once with `for number in (1..4).rev()`. `Countdown` is the same program with
+// the fixed parts made configurable: where it starts, how far each tick goes,
+// how long a tick lasts, what happens on each tick, and a way to call it off.
+//
+//     let mut countdown = Countdown::new(3).delay(Duration::from_secs(1));
+//     countdown.on_tick(|tick| println!("{}!", tick.number));
+//     if countdown.run_for(&mut SystemClock::new()) == Outcome::Liftoff {
+//         println!("LIFTOFF!!!");
+//     }
+//
+// `run_while` and `run_for` behave identically; they differ only in the loop,
+// so the lesson can put the two side by side. Time goes through a `Clock`:
+// `SystemClock` really sleeps, `ManualClock` just moves its hands forward,
+// which lets tests run a ten-second countdown instantly. Either way an
+// `Abort` cuts the current wait short instead of waiting for the next tick.
+
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Where time comes from.
+pub trait Clock {
+    /// Time since the clock started.
+    fn now(&self) -> Duration;
+    /// Waits for `duration`, or less if `abort` is tripped meanwhile.
+    fn sleep(&mut self, duration: Duration, abort: &Abort);
+}
+
+/// Wall-clock time; `sleep` blocks the thread until the time is up or the
+/// countdown is aborted.
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock { start: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&mut self, duration: Duration, abort: &Abort) {
+        abort.wait_timeout(duration);
+    }
+}
+
+/// A clock that only moves when slept on, and never blocks.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ManualClock {
+    pub now: Duration,
+    /// How long each `sleep` call lasted, in order.
+    pub sleeps: Vec<Duration>,
+    /// When to trip the abort, as if someone pressed a button then.
+    pub abort_at: Option<Duration>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        ManualClock::default()
+    }
+
+    /// Trips the abort once the clock reaches `at`, cutting short the
+    /// sleep it falls in.
+    pub fn abort_at(mut self, at: Duration) -> Self {
+        self.abort_at = Some(at);
+        self
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now
+    }
+
+    fn sleep(&mut self, duration: Duration, abort: &Abort) {
+        let slept = match self.abort_at {
+            Some(at) if at <= self.now + duration => {
+                abort.abort();
+                at.saturating_sub(self.now)
+            }
+            _ if abort.is_aborted() => Duration::ZERO,
+            _ => duration,
+        };
+        self.now += slept;
+        self.sleeps.push(slept);
+    }
+}
+
+/// A flag that stops a countdown before its next tick, cutting short the
+/// wait for it. Clones share the flag, so one can be handed to another
+/// thread (or to a tick hook) and tripped from there.
+#[derive(Clone, Debug, Default)]
+pub struct Abort(Arc<(Mutex<bool>, Condvar)>);
+
+impl Abort {
+    pub fn new() -> Self {
+        Abort::default()
+    }
+
+    pub fn abort(&self) {
+        let (aborted, wake) = &*self.0;
+        *aborted.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        wake.notify_all();
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        let (aborted, _) = &*self.0;
+        *aborted.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Blocks until `timeout` has passed or `abort` is called, whichever
+    /// comes first.
+    pub fn wait_timeout(&self, timeout: Duration) {
+        let (aborted, wake) = &*self.0;
+        let guard = aborted.lock().unwrap_or_else(PoisonError::into_inner);
+        let _ = wake.wait_timeout_while(guard, timeout, |aborted| !*aborted);
+    }
+}
+
+/// One step of the countdown, as passed to the hooks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tick {
+    /// The number being called out.
+    pub number: u32,
+    /// 0 for the first tick.
+    pub index: usize,
+    /// Clock time when the tick happened.
+    pub at: Duration,
+}
+
+/// How a run ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Every tick ran.
+    Liftoff,
+    /// Stopped by the abort flag; `next` is the number that wasn't called
+    /// (0 if only the liftoff itself was left).
+    Aborted { next: u32 },
+}
+
+/// `LIFTOFF!!!`, `aborted before 2` or `aborted before liftoff`.
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Liftoff => f.write_str("LIFTOFF!!!"),
+            Outcome::Aborted { next: 0 } => f.write_str("aborted before liftoff"),
+            Outcome::Aborted { next } => write!(f, "aborted before {next}"),
+        }
+    }
+}
+
+/// Called with every tick.
+pub type Hook<'a> = Box<dyn FnMut(&Tick) + 'a>;
+
+/// A countdown from `start` to 1 in steps of `step`.
+pub struct Countdown<'a> {
+    start: u32,
+    step: u32,
+    delay: Duration,
+    abort: Abort,
+    hooks: Vec<Hook<'a>>,
+}
+
+impl<'a> Countdown<'a> {
+    /// Counts `start, start - 1, ..., 1` with no delay.
+    pub fn new(start: u32) -> Self {
+        Countdown { start, step: 1, delay: Duration::ZERO, abort: Abort::new(), hooks: Vec::new() }
+    }
+
+    /// How much each tick subtracts.
+    ///
+    /// Panics if `step` is 0: the countdown would never end.
+    pub fn step(mut self, step: u32) -> Self {
+        assert!(step > 0, "a countdown step of 0 never reaches liftoff");
+        self.step = step;
+        self
+    }
+
+    /// How long to wait after each tick but the last, which goes straight
+    /// on to liftoff.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Stops the countdown once `abort` is tripped.
+    pub fn abort_on(mut self, abort: Abort) -> Self {
+        self.abort = abort;
+        self
+    }
+
+    /// Adds a hook to call on every tick. Hooks run in the order they were
+    /// added, before the tick's delay.
+    pub fn on_tick(&mut self, hook: impl FnMut(&Tick) + 'a) {
+        self.hooks.push(Box::new(hook));
+    }
+
+    /// The numbers this countdown calls out, as an iterator.
+    pub fn numbers(&self) -> impl Iterator<Item = u32> {
+        (1..=self.start).rev().step_by(self.step as usize)
+    }
+
+    fn tick(&mut self, number: u32, index: usize, clock: &mut impl Clock) {
+        let tick = Tick { number, index, at: clock.now() };
+        for hook in &mut self.hooks {
+            hook(&tick);
+        }
+        // Nothing is left to call out after the last number.
+        if number > self.step {
+            clock.sleep(self.delay, &self.abort);
+        }
+    }
+
+    /// Runs the countdown with a `while` loop over a mutable counter, like
+    /// the lesson's `while number != 0`.
+    pub fn run_while(&mut self, clock: &mut impl Clock) -> Outcome {
+        let mut number = self.start;
+        let mut index = 0;
+
+        while number != 0 {
+            if self.abort.is_aborted() {
+                return Outcome::Aborted { next: number };
+            }
+            self.tick(number, index, clock);
+
+            number = number.saturating_sub(self.step);
+            index += 1;
+        }
+
+        self.liftoff()
+    }
+
+    /// Runs the countdown with a `for` loop over `numbers()`, like the
+    /// lesson's `for number in (1..4).rev()`.
+    pub fn run_for(&mut self, clock: &mut impl Clock) -> Outcome {
+        for (index, number) in self.numbers().enumerate() {
+            if self.abort.is_aborted() {
+                return Outcome::Aborted { next: number };
+            }
+            self.tick(number, index, clock);
+        }
+
+        self.liftoff()
+    }
+
+    fn liftoff(&self) -> Outcome {
+        if self.abort.is_aborted() {
+            Outcome::Aborted { next: 0 }
+        } else {
+            Outcome::Liftoff
+        }
+    }
+}
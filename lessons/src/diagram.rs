@@ -0,0 +1,145 @@
+// Stack/heap diagrams
+// Figure 4-2 in the ownership chapter draws a String as three fields on the
+// stack (ptr, len, capacity) pointing at its bytes on the heap. This module
+// draws the same picture for real String values, using their real fields:
+//
+//     let s1 = String::from("hello");
+//     let before = StringParts::of(&s1);
+//     let s2 = s1;
+//     println!("{}", Diagram::new().moved("s1", before).string("s2", &s2));
+//
+// A binding that has been moved from can no longer be read, so its fields are
+// captured with `StringParts::of` before the move and drawn marked as invalid.
+
+use std::fmt;
+
+/// The three stack fields of a `String`, plus a copy of its heap contents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StringParts {
+    pub ptr: usize,
+    pub len: usize,
+    pub capacity: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl StringParts {
+    #[allow(clippy::ptr_arg)] // capacity only exists on String, not str
+    pub fn of(s: &String) -> Self {
+        StringParts { ptr: s.as_ptr() as usize, len: s.len(), capacity: s.capacity(), bytes: s.as_bytes().to_vec() }
+    }
+
+    /// `String::new()` doesn't allocate: its pointer is a placeholder.
+    pub fn has_heap_buffer(&self) -> bool {
+        self.capacity > 0
+    }
+}
+
+struct Slot {
+    name: String,
+    parts: StringParts,
+    moved: bool,
+}
+
+/// A set of `String` bindings drawn as stack frames pointing into the heap.
+#[derive(Default)]
+pub struct Diagram {
+    slots: Vec<Slot>,
+}
+
+impl Diagram {
+    pub fn new() -> Self {
+        Diagram::default()
+    }
+
+    /// Adds a live binding.
+    #[allow(clippy::ptr_arg)]
+    pub fn string(mut self, name: &str, s: &String) -> Self {
+        self.slots.push(Slot { name: name.to_string(), parts: StringParts::of(s), moved: false });
+        self
+    }
+
+    /// Adds a binding that has been moved from, using the fields it had
+    /// before the move.
+    pub fn moved(mut self, name: &str, parts: StringParts) -> Self {
+        self.slots.push(Slot { name: name.to_string(), parts, moved: true });
+        self
+    }
+
+    /// Distinct heap buffers, in the order the stack slots first point at them.
+    fn buffers(&self) -> Vec<&StringParts> {
+        let mut buffers: Vec<&StringParts> = Vec::new();
+        for slot in &self.slots {
+            if slot.parts.has_heap_buffer() && !buffers.iter().any(|b| b.ptr == slot.parts.ptr) {
+                buffers.push(&slot.parts);
+            }
+        }
+        buffers
+    }
+
+    fn stack_column(&self, buffers: &[&StringParts]) -> Vec<String> {
+        let mut lines = vec!["stack".to_string(), String::new()];
+        for slot in &self.slots {
+            let parts = &slot.parts;
+            let arrow = match buffers.iter().position(|b| b.ptr == parts.ptr) {
+                Some(i) if parts.has_heap_buffer() => format!("--> #{}", i + 1),
+                _ => "(no heap buffer)".to_string(),
+            };
+            let status = if slot.moved { " (moved: no longer valid)" } else { "" };
+            lines.push(format!("{}{status}", slot.name));
+            lines.push("+----------+--------------------+".to_string());
+            lines.push(format!("| ptr      | {:#018x} | {arrow}", parts.ptr));
+            lines.push(format!("| len      | {:<18} |", parts.len));
+            lines.push(format!("| capacity | {:<18} |", parts.capacity));
+            lines.push("+----------+--------------------+".to_string());
+            lines.push(String::new());
+        }
+        lines
+    }
+
+    fn heap_column(buffers: &[&StringParts]) -> Vec<String> {
+        let mut lines = vec!["heap".to_string(), String::new()];
+        for (i, buffer) in buffers.iter().enumerate() {
+            lines.push(format!("#{} at {:#018x}", i + 1, buffer.ptr));
+            lines.push("+-------+-------+".to_string());
+            lines.push("| index | value |".to_string());
+            lines.push("+-------+-------+".to_string());
+            for (index, &byte) in buffer.bytes.iter().enumerate() {
+                lines.push(format!("| {index:<5} | {:<5} |", show_byte(byte)));
+            }
+            lines.push("+-------+-------+".to_string());
+            let unused = buffer.capacity - buffer.len;
+            if unused > 0 {
+                lines.push(format!("({unused} more bytes of capacity unused)"));
+            }
+            lines.push(String::new());
+        }
+        lines
+    }
+}
+
+fn show_byte(byte: u8) -> String {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        (byte as char).to_string()
+    } else {
+        format!("{byte:#04x}")
+    }
+}
+
+impl fmt::Display for Diagram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let buffers = self.buffers();
+        let stack = self.stack_column(&buffers);
+        let heap = Diagram::heap_column(&buffers);
+        let width = stack.iter().map(|line| line.chars().count()).max().unwrap_or(0) + 4;
+
+        let rows = stack.len().max(heap.len());
+        // Both columns end with a blank separator line; don't print it twice.
+        for row in 0..rows.saturating_sub(1) {
+            let left = stack.get(row).map_or("", String::as_str);
+            let right = heap.get(row).map_or("", String::as_str);
+            let line = format!("{left:width$}{right}");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+// Divisibility rules
+// The else-if example tests `number % 4`, then `% 3`, then `% 2`, and stops at
+// the first arm that's true. `Classifier` is that chain with the arms as data:
+// an ordered list of divisor -> label rules, applied either first-match (like
+// else-if) or all-match (every rule that divides the number).
+//
+// Each mode is written twice, once as an `if` inside a loop and once as a
+// `match` on slice patterns with guards, the refactoring the lesson suggests
+// for long else-if chains. tests/divisibility.rs checks the two agree on
+// random rule lists.
+
+use std::fmt;
+use std::io;
+use std::ops::RangeInclusive;
+
+/// A divisor and the label a number gets when the divisor divides it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rule {
+    pub divisor: u64,
+    pub label: String,
+}
+
+impl Rule {
+    pub fn new(divisor: u64, label: &str) -> Self {
+        Rule { divisor, label: label.to_string() }
+    }
+
+    pub fn matches(&self, number: u64) -> bool {
+        number.is_multiple_of(self.divisor)
+    }
+}
+
+/// Parses `DIVISOR` or `DIVISOR:LABEL`; the label defaults to
+/// `divisible by DIVISOR`.
+impl std::str::FromStr for Rule {
+    type Err = RuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (divisor, label) = match s.split_once(':') {
+            Some((divisor, label)) => (divisor, Some(label)),
+            None => (s, None),
+        };
+        let divisor: u64 = divisor.trim().parse().map_err(|_| RuleError::BadDivisor(divisor.to_string()))?;
+        if divisor == 0 {
+            return Err(RuleError::ZeroDivisor);
+        }
+        let label = label.map_or_else(|| format!("divisible by {divisor}"), str::to_string);
+        Ok(Rule { divisor, label })
+    }
+}
+
+/// Why a rule list was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuleError {
+    /// `n % 0` panics, so 0 can't be a divisor.
+    ZeroDivisor,
+    BadDivisor(String),
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::ZeroDivisor => write!(
+                f,
+                "a divisor of 0 would panic with \"attempt to calculate the remainder with a divisor of zero\""
+            ),
+            RuleError::BadDivisor(text) => write!(f, "`{text}` isn't a divisor (expected a positive integer)"),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// How many rules a number may match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// The first rule that matches, like an else-if chain.
+    FirstMatch,
+    /// Every rule that matches, in rule order.
+    AllMatches,
+}
+
+/// The rules of the lesson's else-if example, in its order.
+pub fn book_rules() -> Vec<Rule> {
+    [4, 3, 2].map(|d| Rule::new(d, &format!("divisible by {d}"))).to_vec()
+}
+
+/// The lesson's else-if chain, word for word apart from returning the
+/// message instead of printing it.
+#[allow(clippy::manual_is_multiple_of)] // the chain as the lesson writes it
+pub fn book_if_chain(number: u64) -> &'static str {
+    if number % 4 == 0 {
+        "number is divisible by 4"
+    } else if number % 3 == 0 {
+        "number is divisible by 3"
+    } else if number % 2 == 0 {
+        "number is divisible by 2"
+    } else {
+        "number is not divisible by 4, 3, or 2"
+    }
+}
+
+/// The same chain as a `match` on the three remainders.
+pub fn book_match(number: u64) -> &'static str {
+    match (number % 4, number % 3, number % 2) {
+        (0, _, _) => "number is divisible by 4",
+        (_, 0, _) => "number is divisible by 3",
+        (_, _, 0) => "number is divisible by 2",
+        _ => "number is not divisible by 4, 3, or 2",
+    }
+}
+
+/// First-match with `if` in a loop.
+#[allow(clippy::manual_find)] // the if is the point; `find` would hide it
+pub fn first_match_if(rules: &[Rule], number: u64) -> Option<&Rule> {
+    for rule in rules {
+        if rule.matches(number) {
+            return Some(rule);
+        }
+    }
+    None
+}
+
+/// First-match with `match`: the head of the list either matches or the
+/// search goes on in the rest.
+pub fn first_match_match(rules: &[Rule], number: u64) -> Option<&Rule> {
+    match rules {
+        [] => None,
+        [rule, ..] if rule.matches(number) => Some(rule),
+        [_, rest @ ..] => first_match_match(rest, number),
+    }
+}
+
+/// All-match with `if` in a loop.
+pub fn all_matches_if(rules: &[Rule], number: u64) -> Vec<&Rule> {
+    let mut matched = Vec::new();
+    for rule in rules {
+        if rule.matches(number) {
+            matched.push(rule);
+        }
+    }
+    matched
+}
+
+/// All-match with `match`, one rule per step.
+pub fn all_matches_match(rules: &[Rule], number: u64) -> Vec<&Rule> {
+    let mut matched = Vec::new();
+    let mut rest = rules;
+    loop {
+        match rest {
+            [] => return matched,
+            [rule, tail @ ..] if rule.matches(number) => {
+                matched.push(rule);
+                rest = tail;
+            }
+            [_, tail @ ..] => rest = tail,
+        }
+    }
+}
+
+/// The labels one number got.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Classification<'r> {
+    pub number: u64,
+    /// Empty when no rule matched.
+    pub labels: Vec<&'r str>,
+}
+
+/// An ordered rule list and a mode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Classifier {
+    rules: Vec<Rule>,
+    mode: Mode,
+}
+
+impl Classifier {
+    /// Rejects a rule with a divisor of 0.
+    pub fn new(rules: Vec<Rule>, mode: Mode) -> Result<Self, RuleError> {
+        if rules.iter().any(|rule| rule.divisor == 0) {
+            return Err(RuleError::ZeroDivisor);
+        }
+        Ok(Classifier { rules, mode })
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn classify(&self, number: u64) -> Classification<'_> {
+        let matched: Vec<&Rule> = match self.mode {
+            Mode::FirstMatch => first_match_match(&self.rules, number).into_iter().collect(),
+            Mode::AllMatches => all_matches_match(&self.rules, number),
+        };
+        Classification { number, labels: matched.into_iter().map(|rule| rule.label.as_str()).collect() }
+    }
+
+    /// Classifies each number in `range` as the iterator reaches it, so a
+    /// range of any length runs in constant memory.
+    pub fn classify_range(&self, range: RangeInclusive<u64>) -> impl Iterator<Item = Classification<'_>> + '_ {
+        range.map(|number| self.classify(number))
+    }
+
+    /// What a number that matches nothing is: `not divisible by 4, 3, or 2`.
+    pub fn fallback(&self) -> String {
+        let divisors: Vec<String> = self.rules.iter().map(|r| r.divisor.to_string()).collect();
+        match divisors.as_slice() {
+            [] => "no rules".to_string(),
+            [only] => format!("not divisible by {only}"),
+            [a, b] => format!("not divisible by {a} or {b}"),
+            [init @ .., last] => format!("not divisible by {}, or {last}", init.join(", ")),
+        }
+    }
+
+    /// A two-column table, one row per number.
+    pub fn table(&self, range: RangeInclusive<u64>) -> String {
+        let mut out = Vec::new();
+        self.write_table(range, &mut out).expect("writing to a Vec can't fail");
+        String::from_utf8(out).expect("the table is UTF-8")
+    }
+
+    /// Writes `table` row by row instead of building it first.
+    pub fn write_table(&self, range: RangeInclusive<u64>, out: &mut impl io::Write) -> io::Result<()> {
+        // The numbers only go up, so the last one is the widest.
+        let widest = if range.is_empty() { 0 } else { range.end().to_string().len() };
+        let width = widest.max("number".len());
+        let fallback = self.fallback();
+        writeln!(out, "{:width$}  labels", "number")?;
+        for c in self.classify_range(range) {
+            let labels = if c.labels.is_empty() { fallback.clone() } else { c.labels.join(", ") };
+            writeln!(out, "{:>width$}  {labels}", c.number)?;
+        }
+        Ok(())
+    }
+}
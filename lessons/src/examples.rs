@@ -0,0 +1,141 @@
+// Named examples
+// Each lesson registers its snippets in a table instead of keeping a pile of
+// commented-out `fn main()`s. The binary's `main` hands that table to `run`,
+// which picks examples from the command line:
+//
+//     cargo run --bin control_flow                 # every example, in order
+//     cargo run --bin control_flow -- loop-labels  # just one
+//     cargo run --bin control_flow -- --list       # names and descriptions
+//
+// A lesson can also offer commands that take their own arguments, such as
+// `cargo run --bin references_borrowing -- borrowck program.rs`; those are
+// registered in a second table and passed to `run_with_commands`.
+
+use std::process::ExitCode;
+
+/// A runnable snippet from a lesson.
+#[derive(Clone, Copy, Debug)]
+pub struct Example {
+    /// Name used on the command line, e.g. `loop-labels`.
+    pub name: &'static str,
+    /// One-line description shown by `--list`.
+    pub description: &'static str,
+    /// The snippet itself; it prints to stdout.
+    pub run: fn(),
+}
+
+impl Example {
+    pub const fn new(name: &'static str, description: &'static str, run: fn()) -> Self {
+        Example { name, description, run }
+    }
+}
+
+/// A lesson subcommand with arguments of its own.
+#[derive(Clone, Copy, Debug)]
+pub struct Command {
+    pub name: &'static str,
+    /// Argument summary for `--list`, e.g. `FILE`.
+    pub args: &'static str,
+    pub description: &'static str,
+    /// Receives the arguments after the command name.
+    pub run: fn(&[String]) -> ExitCode,
+}
+
+impl Command {
+    pub const fn new(
+        name: &'static str,
+        args: &'static str,
+        description: &'static str,
+        run: fn(&[String]) -> ExitCode,
+    ) -> Self {
+        Command { name, args, description, run }
+    }
+}
+
+/// Looks up an example by name.
+pub fn find<'a>(examples: &'a [Example], name: &str) -> Option<&'a Example> {
+    examples.iter().find(|example| example.name == name)
+}
+
+/// Entry point for a lesson binary: dispatches on `std::env::args`.
+pub fn run(lesson: &str, examples: &[Example]) -> ExitCode {
+    run_with_commands(lesson, examples, &[])
+}
+
+/// Like `run`, for a lesson that also has commands.
+pub fn run_with_commands(lesson: &str, examples: &[Example], commands: &[Command]) -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    dispatch(lesson, examples, commands, &args)
+}
+
+fn dispatch(lesson: &str, examples: &[Example], commands: &[Command], args: &[String]) -> ExitCode {
+    if let Some(command) = args.first().and_then(|name| commands.iter().find(|c| c.name == name)) {
+        return (command.run)(&args[1..]);
+    }
+    match args.first().map(String::as_str) {
+        Some("--list") => {
+            list(examples, commands);
+            ExitCode::SUCCESS
+        }
+        Some("-h" | "--help") => {
+            usage(lesson, commands);
+            ExitCode::SUCCESS
+        }
+        None => {
+            // No names given: show the whole lesson, one example after another.
+            for (i, example) in examples.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                println!("--- {}: {} ---", example.name, example.description);
+                (example.run)();
+            }
+            ExitCode::SUCCESS
+        }
+        Some(_) => {
+            // Check every name before running anything, so a typo in the
+            // second name doesn't leave half the output behind.
+            let mut selected = Vec::with_capacity(args.len());
+            for name in args {
+                match find(examples, name) {
+                    Some(example) => selected.push(example),
+                    None => {
+                        eprintln!("{lesson}: unknown example `{name}` (try --list)");
+                        return ExitCode::from(2);
+                    }
+                }
+            }
+            for example in selected {
+                (example.run)();
+            }
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+fn list(examples: &[Example], commands: &[Command]) {
+    let width = examples.iter().map(|e| e.name.len()).max().unwrap_or(0);
+    for example in examples {
+        println!("{:width$}  {}", example.name, example.description);
+    }
+    if commands.is_empty() {
+        return;
+    }
+    // Examples come first and end at the blank line; tools listing examples rely on it.
+    println!();
+    println!("commands:");
+    let usages: Vec<String> = commands.iter().map(|c| format!("{} {}", c.name, c.args)).collect();
+    let width = usages.iter().map(String::len).max().unwrap_or(0);
+    for (usage, command) in usages.iter().zip(commands) {
+        println!("  {usage:width$}  {}", command.description);
+    }
+}
+
+fn usage(lesson: &str, commands: &[Command]) {
+    println!("usage: {lesson} [--list | EXAMPLE...]");
+    for command in commands {
+        println!("       {lesson} {} {}", command.name, command.args);
+    }
+    println!();
+    println!("With no arguments every example runs in order.");
+}
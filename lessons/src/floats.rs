@@ -0,0 +1,316 @@
+// Floating-point lab
+// The arithmetic example mixes f64 (`95.5 - 4.3`, `56.7 / 32.2`) with integer
+// division (`-5 / 3`). This module prints what's behind those results:
+//
+//   - precision: the same expression in f32 and f64, and the exact value each
+//     one actually stores,
+//   - layout: the sign, exponent and mantissa bits of a value,
+//   - special values: infinities, NaN and signed zero, and the comparison
+//     rules that come with them,
+//   - rounding: `/` and `%` truncate toward zero, while `div_euclid` and
+//     `rem_euclid` round so the remainder is never negative.
+//
+// `report` puts all four sections together.
+
+use std::fmt::{self, Write as _};
+
+/// One expression computed in both float types, printed with `{:?}`, the
+/// shortest text that reads back to the same value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrecisionRow {
+    pub expr: &'static str,
+    pub f32: String,
+    pub f64: String,
+}
+
+pub fn precision_rows() -> Vec<PrecisionRow> {
+    macro_rules! row {
+        ($expr:literal, |$t:ident| $body:expr) => {{
+            let f32 = {
+                type $t = f32;
+                $body
+            };
+            let f64 = {
+                type $t = f64;
+                $body
+            };
+            PrecisionRow { expr: $expr, f32: format!("{f32:?}"), f64: format!("{f64:?}") }
+        }};
+    }
+    vec![
+        row!("95.5 - 4.3", |F| 95.5 as F - 4.3 as F),
+        row!("56.7 / 32.2", |F| 56.7 as F / 32.2 as F),
+        row!("0.1 + 0.2", |F| 0.1 as F + 0.2 as F),
+        row!("0.1 + 0.2 == 0.3", |F| 0.1 as F + 0.2 as F == 0.3 as F),
+        row!("1.0 / 3.0", |F| 1.0 as F / 3.0 as F),
+        row!("16777217.0", |F| 16_777_217.0 as F),
+        row!("1e16 + 1.0", |F| 1e16 as F + 1.0 as F),
+    ]
+}
+
+/// The decimal value `x` really holds, every digit of it: `0.1` is stored as
+/// the nearest binary fraction, which isn't exactly 0.1. A finite float is a
+/// fraction over a power of two, so its decimal expansion always ends.
+pub fn exact(x: f64) -> String {
+    // The smallest subnormal, 2^-1074, needs 1074 places; nothing needs more.
+    let digits = format!("{x:.1074}");
+    if digits.contains('.') {
+        digits.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        digits
+    }
+}
+
+/// `bits` in hex, `digits` long, in groups of four like a Rust literal:
+/// `0x4056_cccc_cccc_cccd`.
+fn hex_bits(bits: u64, digits: usize) -> String {
+    let hex = format!("{bits:0digits$x}");
+    let groups: Vec<&str> = (0..hex.len()).step_by(4).map(|i| &hex[i..(i + 4).min(hex.len())]).collect();
+    format!("0x{}", groups.join("_"))
+}
+
+/// A float split into its IEEE 754 fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FloatBits {
+    /// 32 or 64.
+    pub width: u32,
+    pub sign: u64,
+    /// The stored (biased) exponent.
+    pub exponent: u64,
+    pub mantissa: u64,
+    pub exponent_bits: u32,
+    pub mantissa_bits: u32,
+}
+
+/// What the exponent field says a float is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Zero,
+    Subnormal,
+    Normal,
+    Infinite,
+    Nan,
+}
+
+impl FloatBits {
+    pub fn of_f32(x: f32) -> Self {
+        let bits = u64::from(x.to_bits());
+        FloatBits {
+            width: 32,
+            sign: bits >> 31,
+            exponent: (bits >> 23) & 0xff,
+            mantissa: bits & 0x7f_ffff,
+            exponent_bits: 8,
+            mantissa_bits: 23,
+        }
+    }
+
+    pub fn of_f64(x: f64) -> Self {
+        let bits = x.to_bits();
+        FloatBits {
+            width: 64,
+            sign: bits >> 63,
+            exponent: (bits >> 52) & 0x7ff,
+            mantissa: bits & 0xf_ffff_ffff_ffff,
+            exponent_bits: 11,
+            mantissa_bits: 52,
+        }
+    }
+
+    /// 127 for f32, 1023 for f64.
+    pub fn bias(&self) -> i64 {
+        (1 << (self.exponent_bits - 1)) - 1
+    }
+
+    pub fn kind(&self) -> Kind {
+        let all_ones = (1 << self.exponent_bits) - 1;
+        match (self.exponent, self.mantissa) {
+            (0, 0) => Kind::Zero,
+            (0, _) => Kind::Subnormal,
+            (e, 0) if e == all_ones => Kind::Infinite,
+            (e, _) if e == all_ones => Kind::Nan,
+            _ => Kind::Normal,
+        }
+    }
+
+    /// The power of two the value is scaled by, for finite non-zero values.
+    pub fn unbiased_exponent(&self) -> Option<i64> {
+        match self.kind() {
+            Kind::Normal => Some(self.exponent as i64 - self.bias()),
+            // Subnormals use the smallest normal exponent, without the implicit 1.
+            Kind::Subnormal => Some(1 - self.bias()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FloatBits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let FloatBits { sign, exponent, mantissa, exponent_bits: e, mantissa_bits: m, .. } = *self;
+        writeln!(f, "  sign     {sign}  ({})", if sign == 1 { "negative" } else { "positive" })?;
+        write!(f, "  exponent {exponent:0e$b}  (stored {exponent}", e = e as usize)?;
+        match (self.kind(), self.unbiased_exponent()) {
+            (Kind::Normal, Some(power)) => writeln!(f, ", minus bias {} = 2^{power})", self.bias())?,
+            (Kind::Subnormal, Some(power)) => writeln!(f, ": subnormal, 2^{power} with no implicit 1)")?,
+            (Kind::Zero, _) => writeln!(f, ": zero)")?,
+            (Kind::Infinite, _) => writeln!(f, ": all ones and mantissa 0, infinity)")?,
+            (Kind::Nan, _) => writeln!(f, ": all ones and mantissa not 0, NaN)")?,
+            _ => writeln!(f, ")")?,
+        }
+        let implicit = if self.kind() == Kind::Normal { "1." } else { "0." };
+        writeln!(f, "  mantissa {mantissa:0m$b}  (significand {implicit}{mantissa:0m$b} in binary)", m = m as usize)
+    }
+}
+
+/// `x` as f64, its fields, and the exact value it stores.
+pub fn describe_f64(x: f64) -> String {
+    let bits = FloatBits::of_f64(x);
+    let mut out = String::new();
+    writeln!(out, "{x:?}_f64 = {}", hex_bits(x.to_bits(), 16)).unwrap();
+    write!(out, "{bits}").unwrap();
+    if x.is_finite() {
+        writeln!(out, "  exactly  {}", exact(x)).unwrap();
+    }
+    out
+}
+
+/// `x` as f32 and its fields.
+pub fn describe_f32(x: f32) -> String {
+    let bits = FloatBits::of_f32(x);
+    format!("{x:?}_f32 = {}\n{bits}", hex_bits(u64::from(x.to_bits()), 8))
+}
+
+/// Expressions with infinities, NaN and signed zeros, and what they give.
+#[allow(clippy::eq_op)] // `x / x` and `x - x` are the point here
+pub fn special_values() -> Vec<(&'static str, String)> {
+    let nan = f64::NAN;
+    let inf = f64::INFINITY;
+    // Read through black_box so the results come from running the code, not
+    // from constant folding.
+    let zero = std::hint::black_box(0.0_f64);
+    vec![
+        ("1.0 / 0.0", format!("{:?}", 1.0 / zero)),
+        ("-1.0 / 0.0", format!("{:?}", -1.0 / zero)),
+        ("0.0 / 0.0", format!("{:?}", zero / zero)),
+        ("f64::INFINITY - f64::INFINITY", format!("{:?}", inf - inf)),
+        ("f64::INFINITY * 0.0", format!("{:?}", inf * zero)),
+        ("f64::MAX * 2.0", format!("{:?}", f64::MAX * 2.0)),
+        ("f64::NAN == f64::NAN", format!("{:?}", nan == std::hint::black_box(nan))),
+        ("f64::NAN != f64::NAN", format!("{:?}", nan != std::hint::black_box(nan))),
+        ("f64::NAN < 1.0 || f64::NAN >= 1.0", format!("{:?}", nan < 1.0 || nan >= 1.0)),
+        ("f64::NAN.partial_cmp(&1.0)", format!("{:?}", nan.partial_cmp(&1.0))),
+        ("f64::NAN.total_cmp(&f64::INFINITY)", format!("{:?}", nan.total_cmp(&inf))),
+        ("f64::NAN.max(1.0)", format!("{:?}", nan.max(1.0))),
+        ("f64::NAN.is_nan()", format!("{:?}", nan.is_nan())),
+        ("-0.0 == 0.0", format!("{:?}", -zero == zero)),
+        ("1.0 / -0.0", format!("{:?}", 1.0 / -zero)),
+        ("(-0.0_f64).to_bits() == 0.0_f64.to_bits()", format!("{:?}", (-zero).to_bits() == zero.to_bits())),
+        ("f64::NAN as i32", format!("{:?}", std::hint::black_box(nan) as i32)),
+        ("f64::INFINITY as u8", format!("{:?}", std::hint::black_box(inf) as u8)),
+    ]
+}
+
+/// `a / b` and `a % b` next to their Euclidean versions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DivisionRow {
+    pub a: i32,
+    pub b: i32,
+    pub quotient: i32,
+    pub remainder: i32,
+    pub div_euclid: i32,
+    pub rem_euclid: i32,
+}
+
+/// Every sign combination of 7 and 3 (and -5 / 3 from the lesson).
+pub fn division_rows() -> Vec<DivisionRow> {
+    [(-5, 3), (7, 3), (-7, 3), (7, -3), (-7, -3)]
+        .into_iter()
+        .map(|(a, b): (i32, i32)| DivisionRow {
+            a,
+            b,
+            quotient: a / b,
+            remainder: a % b,
+            div_euclid: a.div_euclid(b),
+            rem_euclid: a.rem_euclid(b),
+        })
+        .collect()
+}
+
+pub fn precision_report() -> String {
+    let rows = precision_rows();
+    let width = rows.iter().map(|row| row.expr.len()).max().unwrap_or(0);
+    let f32_width = rows.iter().map(|row| row.f32.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    writeln!(out, "{:width$}  {:f32_width$}  f64", "", "f32").unwrap();
+    for row in &rows {
+        writeln!(out, "{:width$}  {:f32_width$}  {}", row.expr, row.f32, row.f64).unwrap();
+    }
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "f32 keeps {} significant bits (about {} decimal digits), f64 keeps {} (about {}).",
+        f32::MANTISSA_DIGITS,
+        f32::DIGITS,
+        f64::MANTISSA_DIGITS,
+        f64::DIGITS
+    )
+    .unwrap();
+    writeln!(out, "f32::EPSILON = {:e}, f64::EPSILON = {:e}", f32::EPSILON, f64::EPSILON).unwrap();
+    writeln!(out, "0.1 is stored as {}", exact(0.1)).unwrap();
+    writeln!(out, "0.1_f32 is stored as {:.30}", 0.1_f32).unwrap();
+    out
+}
+
+pub fn layout_report() -> String {
+    let mut out = String::new();
+    for x in [91.2, -0.1, 0.0] {
+        out.push_str(&describe_f64(x));
+    }
+    out.push_str(&describe_f32(91.2));
+    out
+}
+
+pub fn special_values_report() -> String {
+    let rows = special_values();
+    let width = rows.iter().map(|(expr, _)| expr.len()).max().unwrap_or(0);
+    rows.iter().map(|(expr, value)| format!("{expr:width$}  {value}\n")).collect()
+}
+
+pub fn division_report() -> String {
+    let mut out = String::new();
+    writeln!(out, "   a   b   a / b   a % b   div_euclid   rem_euclid").unwrap();
+    for DivisionRow { a, b, quotient, remainder, div_euclid, rem_euclid } in division_rows() {
+        writeln!(out, "{a:>4}{b:>4}{quotient:>8}{remainder:>8}{div_euclid:>13}{rem_euclid:>13}").unwrap();
+    }
+    writeln!(out).unwrap();
+    writeln!(out, "/ truncates toward zero and % takes the sign of a; rem_euclid is never negative, and").unwrap();
+    writeln!(out, "a == b * a.div_euclid(b) + a.rem_euclid(b). Floats follow the same rules:").unwrap();
+    writeln!(
+        out,
+        "-5.0 % 3.0 = {:?}, (-5.0_f64).rem_euclid(3.0) = {:?}, (-5.0_f64).div_euclid(3.0) = {:?}",
+        -5.0 % 3.0,
+        (-5.0_f64).rem_euclid(3.0),
+        (-5.0_f64).div_euclid(3.0)
+    )
+    .unwrap();
+    out
+}
+
+/// Every section, with headings.
+pub fn report() -> String {
+    let sections = [
+        ("f32 vs f64", precision_report()),
+        ("bit layout", layout_report()),
+        ("infinity, NaN and -0.0", special_values_report()),
+        ("integer division and remainder", division_report()),
+    ];
+    let mut out = String::new();
+    for (i, (title, body)) in sections.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        writeln!(out, "== {title} ==").unwrap();
+        out.push_str(body);
+    }
+    out
+}
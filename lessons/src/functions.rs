@@ -0,0 +1,25 @@
+// Functions
+// The functions lesson calls these from its examples; they live here so the
+// claims in the lesson (what each returns, what gets printed) can be tested.
+
+/// Prints the value of `x` followed by its unit label, e.g. `5h`.
+pub fn another_function(x: i32, unit_label: char) {
+    println!("{}", another_function_message(x, unit_label));
+}
+
+/// The line `another_function` prints.
+pub fn another_function_message(x: i32, unit_label: char) -> String {
+    format!("Another function.value is: {x}{unit_label}")
+}
+
+/// Returns 5. The body is a lonely `5` with no semicolon: an expression,
+/// so it is the function's return value.
+pub fn five() -> i32 {
+    5
+}
+
+/// Returns `x + 1`. Adding a semicolon after `x + 1` would turn it into a
+/// statement and the function would no longer return an `i32`.
+pub fn plus_one(x: i32) -> i32 {
+    x + 1
+}
@@ -0,0 +1,330 @@
+// Integer literals
+// Parses the literal forms from the data-types lesson's table: decimal
+// `98_222`, hex `0xff`, octal `0o77`, binary `0b1111_0000` and byte `b'A'`,
+// with an optional type suffix as in `57u8` or `57_u8`. Without a suffix the
+// type is i32, as rustc infers when nothing else constrains it (a byte
+// literal is always u8). Values that don't fit are rejected with the message
+// rustc's `overflowing_literals` lint prints, e.g. for `256u8`.
+//
+// A leading `-` is accepted too. Strictly it's the negation operator, not
+// part of the literal, but rustc checks `-128i8` as a whole, so we do too.
+
+use std::fmt;
+
+use super::ranges::min_max;
+use super::IntType;
+
+/// How the literal is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Form {
+    Decimal,
+    Hex,
+    Octal,
+    Binary,
+    Byte,
+}
+
+impl Form {
+    pub fn name(self) -> &'static str {
+        match self {
+            Form::Decimal => "decimal",
+            Form::Hex => "hex",
+            Form::Octal => "octal",
+            Form::Binary => "binary",
+            Form::Byte => "byte",
+        }
+    }
+
+    fn radix(self) -> u32 {
+        match self {
+            Form::Decimal | Form::Byte => 10,
+            Form::Hex => 16,
+            Form::Octal => 8,
+            Form::Binary => 2,
+        }
+    }
+}
+
+impl fmt::Display for Form {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A literal's value, held in the widest type of its signedness.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Value {
+    Signed(i128),
+    Unsigned(u128),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Signed(v) => write!(f, "{v}"),
+            Value::Unsigned(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+/// A successfully parsed literal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Literal {
+    pub text: String,
+    pub form: Form,
+    pub ty: IntType,
+    /// Whether the type came from a suffix rather than the i32 default.
+    pub suffixed: bool,
+    pub value: Value,
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let why = match (self.form, self.suffixed) {
+            (Form::Byte, _) => "byte literals are always u8",
+            (_, true) => "from the suffix",
+            (_, false) => "no suffix, so the default i32",
+        };
+        writeln!(f, "{}", self.text)?;
+        writeln!(f, "  form:  {}", self.form)?;
+        writeln!(f, "  type:  {} ({why})", self.ty)?;
+        writeln!(f, "  value: {}", self.value)
+    }
+}
+
+/// Why a literal was rejected, worded like rustc's error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LiteralError {
+    /// rustc's error code, if the error has one.
+    pub code: Option<&'static str>,
+    pub message: String,
+    pub note: Option<String>,
+    pub help: Option<String>,
+}
+
+impl LiteralError {
+    fn new(message: impl Into<String>) -> Self {
+        LiteralError { code: None, message: message.into(), note: None, help: None }
+    }
+
+    fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code {
+            Some(code) => writeln!(f, "error[{code}]: {}", self.message)?,
+            None => writeln!(f, "error: {}", self.message)?,
+        }
+        if let Some(note) = &self.note {
+            writeln!(f, "  = note: {note}")?;
+        }
+        if let Some(help) = &self.help {
+            writeln!(f, "  = help: {help}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for LiteralError {}
+
+/// Parses an integer literal as written in Rust source.
+pub fn parse(text: &str) -> Result<Literal, LiteralError> {
+    let text = text.trim();
+    let (negative, unsigned_text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, text),
+    };
+
+    let (form, magnitude, ty, suffixed) = if let Some(byte) = unsigned_text.strip_prefix("b'") {
+        (Form::Byte, u128::from(byte_literal(byte)?), IntType::U8, true)
+    } else {
+        let (form, body) = split_prefix(unsigned_text);
+        let (digits, suffix) = split_suffix(form, body);
+        if form == Form::Decimal && !digits.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(LiteralError::new(format!("expected an integer literal, found `{text}`")));
+        }
+        let ty = match suffix {
+            "" => None,
+            suffix if form == Form::Decimal && (matches!(suffix, "f32" | "f64") || suffix.starts_with(['.', 'e'])) => {
+                return Err(LiteralError::new(format!("`{text}` is a float literal, not an integer literal")));
+            }
+            suffix => Some(suffix.parse::<IntType>().map_err(|_| {
+                LiteralError::new(format!("invalid suffix `{suffix}` for number literal"))
+                    .with_help("the suffix must be one of the numeric types (`u32`, `isize`, `f32`, etc.)")
+            })?),
+        };
+        let magnitude = digits_value(form, digits)?;
+        (form, magnitude, ty.unwrap_or(IntType::I32), ty.is_some())
+    };
+
+    if negative && !ty.is_signed() {
+        return Err(LiteralError::new(format!("cannot apply unary operator `-` to type `{ty}`"))
+            .with_code("E0600")
+            .with_note("unsigned values cannot be negated"));
+    }
+
+    let (min, max) = min_max(ty);
+    let in_range = if negative { magnitude <= min.unsigned_abs() } else { magnitude <= max };
+    if !in_range {
+        return Err(out_of_range(text, form, negative, magnitude, ty));
+    }
+
+    let value = match (ty.is_signed(), negative) {
+        (false, _) => Value::Unsigned(magnitude),
+        // magnitude ≤ |i128::MIN| here, so this can't overflow.
+        (true, true) => Value::Signed(0i128.wrapping_sub_unsigned(magnitude)),
+        (true, false) => Value::Signed(magnitude as i128),
+    };
+    Ok(Literal { text: text.to_string(), form, ty, suffixed, value })
+}
+
+/// `0x`, `0o` or `0b` and the rest; decimal if there is no prefix.
+fn split_prefix(text: &str) -> (Form, &str) {
+    for (prefix, form) in [("0x", Form::Hex), ("0o", Form::Octal), ("0b", Form::Binary)] {
+        if let Some(rest) = text.strip_prefix(prefix) {
+            return (form, rest);
+        }
+    }
+    (Form::Decimal, text)
+}
+
+/// Digits and suffix. The suffix starts at the first `i`, `u` or (for
+/// decimals) `f`, or at the first character that can't be a digit at all.
+fn split_suffix(form: Form, body: &str) -> (&str, &str) {
+    let at = body
+        .char_indices()
+        .find(|&(_, c)| match c {
+            '_' => false,
+            'i' | 'u' => true,
+            'f' | 'e' if form == Form::Decimal => true,
+            c => !c.is_ascii_hexdigit() || (form != Form::Hex && c.is_ascii_alphabetic()),
+        })
+        .map_or(body.len(), |(i, _)| i);
+    let (digits, suffix) = body.split_at(at);
+    // `57_u8`: the separator belongs to neither part.
+    (digits.trim_end_matches('_'), suffix)
+}
+
+fn digits_value(form: Form, digits: &str) -> Result<u128, LiteralError> {
+    let radix = form.radix();
+    let mut value: u128 = 0;
+    let mut any = false;
+    for c in digits.chars() {
+        if c == '_' {
+            continue;
+        }
+        let digit =
+            c.to_digit(radix).ok_or_else(|| LiteralError::new(format!("invalid digit for a base {radix} literal")))?;
+        value = value.checked_mul(radix.into()).and_then(|v| v.checked_add(digit.into())).ok_or_else(|| {
+            LiteralError::new("integer literal is too large")
+                .with_note(format!("value exceeds limit of `{}`", u128::MAX))
+        })?;
+        any = true;
+    }
+    if any {
+        Ok(value)
+    } else {
+        Err(LiteralError::new("no valid digits found for number").with_code("E0768"))
+    }
+}
+
+/// The rest of a byte literal after `b'`: one ASCII character or an escape,
+/// then the closing quote.
+fn byte_literal(rest: &str) -> Result<u8, LiteralError> {
+    let body = rest.strip_suffix('\'').ok_or_else(|| LiteralError::new("unterminated byte constant"))?;
+    let byte = match body.as_bytes() {
+        [] => return Err(LiteralError::new("empty byte literal")),
+        [b'\\', b'n'] => b'\n',
+        [b'\\', b'r'] => b'\r',
+        [b'\\', b't'] => b'\t',
+        [b'\\', b'\\'] => b'\\',
+        [b'\\', b'0'] => b'\0',
+        [b'\\', b'\''] => b'\'',
+        [b'\\', b'"'] => b'"',
+        [b'\\', b'x', hex @ ..] if hex.len() == 2 => std::str::from_utf8(hex)
+            .ok()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .ok_or_else(|| LiteralError::new("invalid character in numeric character escape"))?,
+        [b'\\', ..] => return Err(LiteralError::new(format!("unknown byte escape: `{body}`"))),
+        [b'\''] => return Err(LiteralError::new("byte constant must be escaped: `'`")),
+        [byte] if byte.is_ascii() => *byte,
+        _ if body.chars().count() == 1 => return Err(LiteralError::new("non-ASCII character in byte literal")),
+        _ => return Err(LiteralError::new("character literal may only contain one codepoint")),
+    };
+    Ok(byte)
+}
+
+/// rustc's `overflowing_literals` error for a literal that doesn't fit `ty`.
+fn out_of_range(text: &str, form: Form, negative: bool, magnitude: u128, ty: IntType) -> LiteralError {
+    let (min, max) = min_max(ty);
+    let error = LiteralError::new(format!("literal out of range for `{ty}`"));
+    if !matches!(form, Form::Hex | Form::Binary) {
+        let error = error.with_note(format!(
+            "the literal `{text}` does not fit into the type `{ty}` whose range is `{min}..={max}`"
+        ));
+        // rustc only suggests another type here for signed literals.
+        return match suggest(ty, negative, magnitude) {
+            Some(wider) if ty.is_signed() => error.with_help(format!("consider using the type `{wider}` instead")),
+            _ => error,
+        };
+    }
+    // Written as a bit pattern, rustc shows the decimal value and what the
+    // bits would be truncated to.
+    let bits = ty.bits();
+    let truncated = if bits == 128 { magnitude } else { magnitude & ((1u128 << bits) - 1) };
+    let becomes = if ty.is_signed() && truncated >> (bits - 1) == 1 {
+        // Sign-extend the top bit.
+        let shift = 128 - bits;
+        (((truncated << shift) as i128) >> shift).to_string()
+    } else {
+        truncated.to_string()
+    };
+    let sign = if negative { "-" } else { "" };
+    let error = error.with_note(format!(
+        "the literal `{text}` (decimal `{sign}{magnitude}`) does not fit into the type `{ty}` and will become `{becomes}{ty}`"
+    ));
+    match suggest(ty, negative, magnitude) {
+        Some(wider) => error.with_help(format!("consider using the type `{wider}` instead")),
+        None => error,
+    }
+}
+
+/// The type rustc suggests instead of `ty`: none for isize and usize; the
+/// smallest unsigned type that fits for an unsigned `ty`; and for a signed
+/// `ty`, the unsigned type of the same width if that fits (`0xFFi8` → u8),
+/// otherwise the smallest signed type that does.
+fn suggest(ty: IntType, negative: bool, magnitude: u128) -> Option<IntType> {
+    const SIGNED: [IntType; 5] = [IntType::I8, IntType::I16, IntType::I32, IntType::I64, IntType::I128];
+    const UNSIGNED: [IntType; 5] = [IntType::U8, IntType::U16, IntType::U32, IntType::U64, IntType::U128];
+    let fits = |candidate: &&IntType| {
+        let (min, max) = min_max(**candidate);
+        if negative {
+            magnitude <= min.unsigned_abs()
+        } else {
+            magnitude <= max
+        }
+    };
+    match ty {
+        IntType::Isize | IntType::Usize => None,
+        _ if !ty.is_signed() => UNSIGNED.iter().find(fits).copied(),
+        _ if negative => SIGNED.iter().find(fits).copied(),
+        _ => match UNSIGNED.iter().find(fits) {
+            Some(&unsigned) if unsigned.bits() == ty.bits() => Some(unsigned),
+            _ => SIGNED.iter().find(fits).copied(),
+        },
+    }
+}
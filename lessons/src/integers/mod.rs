@@ -0,0 +1,109 @@
+// Integer types
+// The data-types lesson lists twelve integer types: i8 to i128 and u8 to u128,
+// plus isize and usize, whose size depends on the target. `IntType` names them
+// so the tools in the submodules can take a type on the command line:
+//
+//   - `literal`: parses integer literals the way rustc reads them,
+//   - `overflow`: what each overflow strategy gives for an operation,
+//   - `ranges`: each type's range, as a table built from `MIN` and `MAX`.
+
+pub mod literal;
+pub mod overflow;
+pub mod ranges;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// One of Rust's integer types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IntType {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+impl IntType {
+    /// Every integer type, signed first, in increasing size.
+    pub const ALL: [IntType; 12] = [
+        IntType::I8,
+        IntType::I16,
+        IntType::I32,
+        IntType::I64,
+        IntType::I128,
+        IntType::Isize,
+        IntType::U8,
+        IntType::U16,
+        IntType::U32,
+        IntType::U64,
+        IntType::U128,
+        IntType::Usize,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Isize => "isize",
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::Usize => "usize",
+        }
+    }
+
+    /// Width in bits; for `isize` and `usize`, the width on this target.
+    pub fn bits(self) -> u32 {
+        match self {
+            IntType::I8 | IntType::U8 => 8,
+            IntType::I16 | IntType::U16 => 16,
+            IntType::I32 | IntType::U32 => 32,
+            IntType::I64 | IntType::U64 => 64,
+            IntType::I128 | IntType::U128 => 128,
+            IntType::Isize | IntType::Usize => usize::BITS,
+        }
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(self, IntType::I8 | IntType::I16 | IntType::I32 | IntType::I64 | IntType::I128 | IntType::Isize)
+    }
+}
+
+impl fmt::Display for IntType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The name isn't one of the twelve integer types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownType(pub String);
+
+impl fmt::Display for UnknownType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not an integer type (expected i8..i128, u8..u128, isize or usize)", self.0)
+    }
+}
+
+impl std::error::Error for UnknownType {}
+
+impl FromStr for IntType {
+    type Err = UnknownType;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        IntType::ALL.into_iter().find(|ty| ty.name() == name).ok_or_else(|| UnknownType(name.to_string()))
+    }
+}
@@ -0,0 +1,298 @@
+// Integer overflow explorer
+// Runs one operation on two operands of a chosen integer type through each
+// of the standard library's overflow strategies (`wrapping_*`, `checked_*`,
+// `overflowing_*`, `saturating_*`) and works out what the plain operator
+// would do: panic in a debug build, wrap in a release build (overflow checks
+// are off there), except for division and remainder, which always check.
+
+use std::fmt::{self, Debug, Display};
+use std::str::FromStr;
+
+use super::IntType;
+
+/// The operations the explorer knows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    /// `a.pow(b)`; the exponent is always a `u32`.
+    Pow,
+}
+
+impl Op {
+    pub const ALL: [Op; 6] = [Op::Add, Op::Sub, Op::Mul, Op::Div, Op::Rem, Op::Pow];
+
+    /// Method suffix, as in `wrapping_add`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "sub",
+            Op::Mul => "mul",
+            Op::Div => "div",
+            Op::Rem => "rem",
+            Op::Pow => "pow",
+        }
+    }
+
+    /// How the plain operation is written, e.g. `200 + 100` or `2.pow(8)`.
+    pub fn plain(self, lhs: &str, rhs: &str) -> String {
+        let symbol = match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Rem => "%",
+            Op::Pow => return format!("{lhs}.pow({rhs})"),
+        };
+        format!("{lhs} {symbol} {rhs}")
+    }
+
+    /// The message the operator panics with when the result overflows.
+    fn overflow_panic(self) -> &'static str {
+        match self {
+            Op::Add => "attempt to add with overflow",
+            Op::Sub => "attempt to subtract with overflow",
+            Op::Mul | Op::Pow => "attempt to multiply with overflow",
+            Op::Div => "attempt to divide with overflow",
+            Op::Rem => "attempt to calculate the remainder with overflow",
+        }
+    }
+
+    fn zero_divisor_panic(self) -> &'static str {
+        match self {
+            Op::Rem => "attempt to calculate the remainder with a divisor of zero",
+            _ => "attempt to divide by zero",
+        }
+    }
+
+    /// Division and remainder panic on overflow even with overflow checks off.
+    fn always_checked(self) -> bool {
+        matches!(self, Op::Div | Op::Rem)
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Op {
+    type Err = String;
+
+    /// Accepts the name (`add`) or the operator (`+`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let op = match s {
+            "add" | "+" => Op::Add,
+            "sub" | "-" => Op::Sub,
+            "mul" | "*" => Op::Mul,
+            "div" | "/" => Op::Div,
+            "rem" | "%" => Op::Rem,
+            "pow" => Op::Pow,
+            _ => return Err(format!("unknown operation `{s}` (expected add, sub, mul, div, rem or pow)")),
+        };
+        Ok(op)
+    }
+}
+
+/// What one strategy produces: the value as Rust's `Debug` prints it, or
+/// why there is none (a panic, or a method std doesn't have).
+pub type Outcome = Result<String, String>;
+
+/// The result of `explore`: every strategy for one operation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Exploration {
+    pub op: Op,
+    pub ty: IntType,
+    pub lhs: String,
+    pub rhs: String,
+    /// Whether the true result is out of range (or the divisor is zero).
+    pub overflows: bool,
+    pub wrapping: Outcome,
+    pub checked: Outcome,
+    pub overflowing: Outcome,
+    pub saturating: Outcome,
+    /// The plain operator with overflow checks on (the default debug build).
+    pub debug: Outcome,
+    /// The plain operator with overflow checks off (the default release build).
+    pub release: Outcome,
+}
+
+/// Runs `lhs op rhs` as `ty` through every strategy. Fails if an operand
+/// doesn't parse as `ty` (or, for `pow`, the exponent as `u32`).
+pub fn explore(op: Op, lhs: &str, rhs: &str, ty: IntType) -> Result<Exploration, String> {
+    macro_rules! explore_as {
+        ($t:ty) => {{
+            let a: $t = operand(lhs, ty.name(), <$t>::MIN, <$t>::MAX)?;
+            // Only `pow` takes a `u32`; every other operation takes a second `$t`.
+            let b = || operand::<$t>(rhs, ty.name(), <$t>::MIN, <$t>::MAX);
+            match op {
+                Op::Add => run(
+                    op,
+                    a,
+                    b()?,
+                    (<$t>::wrapping_add, <$t>::checked_add, <$t>::overflowing_add, Some(<$t>::saturating_add)),
+                ),
+                Op::Sub => run(
+                    op,
+                    a,
+                    b()?,
+                    (<$t>::wrapping_sub, <$t>::checked_sub, <$t>::overflowing_sub, Some(<$t>::saturating_sub)),
+                ),
+                Op::Mul => run(
+                    op,
+                    a,
+                    b()?,
+                    (<$t>::wrapping_mul, <$t>::checked_mul, <$t>::overflowing_mul, Some(<$t>::saturating_mul)),
+                ),
+                Op::Div => match b()? {
+                    0 => zero_divisor(op),
+                    b => run(
+                        op,
+                        a,
+                        b,
+                        (<$t>::wrapping_div, <$t>::checked_div, <$t>::overflowing_div, Some(<$t>::saturating_div)),
+                    ),
+                },
+                Op::Rem => match b()? {
+                    0 => zero_divisor(op),
+                    b => run(op, a, b, (<$t>::wrapping_rem, <$t>::checked_rem, <$t>::overflowing_rem, None)),
+                },
+                Op::Pow => {
+                    let exponent: u32 = operand(rhs, "u32", u32::MIN, u32::MAX)?;
+                    run(
+                        op,
+                        a,
+                        exponent,
+                        (<$t>::wrapping_pow, <$t>::checked_pow, <$t>::overflowing_pow, Some(<$t>::saturating_pow)),
+                    )
+                }
+            }
+        }};
+    }
+
+    let strategies = match ty {
+        IntType::I8 => explore_as!(i8),
+        IntType::I16 => explore_as!(i16),
+        IntType::I32 => explore_as!(i32),
+        IntType::I64 => explore_as!(i64),
+        IntType::I128 => explore_as!(i128),
+        IntType::Isize => explore_as!(isize),
+        IntType::U8 => explore_as!(u8),
+        IntType::U16 => explore_as!(u16),
+        IntType::U32 => explore_as!(u32),
+        IntType::U64 => explore_as!(u64),
+        IntType::U128 => explore_as!(u128),
+        IntType::Usize => explore_as!(usize),
+    };
+    let Strategies { overflows, zero_divisor, wrapping, checked, overflowing, saturating } = strategies;
+
+    let (debug, release) = if !overflows {
+        // In range: the operator agrees with every strategy.
+        (wrapping.clone(), wrapping.clone())
+    } else if zero_divisor {
+        let panic = Err(format!("panics: {}", op.zero_divisor_panic()));
+        (panic.clone(), panic)
+    } else {
+        let panic = Err(format!("panics: {}", op.overflow_panic()));
+        let release = if op.always_checked() { panic.clone() } else { wrapping.clone() };
+        (panic, release)
+    };
+
+    Ok(Exploration {
+        op,
+        ty,
+        lhs: lhs.trim().to_string(),
+        rhs: rhs.trim().to_string(),
+        overflows,
+        wrapping,
+        checked,
+        overflowing,
+        saturating,
+        debug,
+        release,
+    })
+}
+
+struct Strategies {
+    overflows: bool,
+    zero_divisor: bool,
+    wrapping: Outcome,
+    checked: Outcome,
+    overflowing: Outcome,
+    saturating: Outcome,
+}
+
+/// The `wrapping_*`, `checked_*`, `overflowing_*` and, if std has it,
+/// `saturating_*` versions of one operation.
+type Methods<T, R> = (fn(T, R) -> T, fn(T, R) -> Option<T>, fn(T, R) -> (T, bool), Option<fn(T, R) -> T>);
+
+fn run<T: Copy + Debug, R: Copy>(op: Op, a: T, b: R, methods: Methods<T, R>) -> Strategies {
+    let (wrapping, checked, overflowing, saturating) = methods;
+    let checked = checked(a, b);
+    Strategies {
+        overflows: checked.is_none(),
+        zero_divisor: false,
+        wrapping: Ok(format!("{:?}", wrapping(a, b))),
+        checked: Ok(format!("{checked:?}")),
+        overflowing: Ok(format!("{:?}", overflowing(a, b))),
+        saturating: match saturating {
+            Some(saturating) => Ok(format!("{:?}", saturating(a, b))),
+            None => Err(format!("std has no saturating_{op}")),
+        },
+    }
+}
+
+/// Every strategy but `checked_*` panics when dividing by zero.
+fn zero_divisor(op: Op) -> Strategies {
+    let panic = Err(format!("panics: {}", op.zero_divisor_panic()));
+    Strategies {
+        overflows: true,
+        zero_divisor: true,
+        wrapping: panic.clone(),
+        checked: Ok("None".to_string()),
+        overflowing: panic.clone(),
+        saturating: if op == Op::Rem { Err("std has no saturating_rem".to_string()) } else { panic },
+    }
+}
+
+/// Parses `text` as an integer of the named type, allowing `_` separators.
+fn operand<T: FromStr + Display>(text: &str, ty: &str, min: T, max: T) -> Result<T, String> {
+    let digits: String = text.trim().chars().filter(|&c| c != '_').collect();
+    digits.parse().map_err(|_| {
+        if digits.parse::<i128>().is_ok() || digits.parse::<u128>().is_ok() {
+            format!("`{}` doesn't fit in {ty} ({min}..={max})", text.trim())
+        } else {
+            format!("`{}` is not an integer", text.trim())
+        }
+    })
+}
+
+impl Display for Exploration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Exploration { op, ty, lhs, rhs, .. } = self;
+        let args = if *op == Op::Pow { format!("{lhs}_{ty}, {rhs}_u32") } else { format!("{lhs}_{ty}, {rhs}_{ty}") };
+        let plain = op.plain(lhs, rhs);
+        let rows = [
+            (format!("wrapping_{op}"), &self.wrapping),
+            (format!("checked_{op}"), &self.checked),
+            (format!("overflowing_{op}"), &self.overflowing),
+            (format!("saturating_{op}"), &self.saturating),
+            (format!("{plain} (debug)"), &self.debug),
+            (format!("{plain} (release)"), &self.release),
+        ];
+        let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+
+        writeln!(f, "{op}({args})")?;
+        for (label, outcome) in rows {
+            match outcome {
+                Ok(value) => writeln!(f, "  {label:width$}  {value}")?,
+                Err(reason) => writeln!(f, "  {label:width$}  {reason}")?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+// Integer ranges
+// The data-types notes give each type's range as a formula in n, the number
+// of bits. Here the table is built from the types' real `MIN` and `MAX`
+// constants instead, so it can't disagree with the compiler, and
+// `lesson_notes` renders the lesson's own paragraph from the same numbers
+// (tests/integer_ranges.rs checks the lesson still matches it).
+
+use std::fmt::Write as _;
+
+use super::IntType;
+
+/// Output style for `table`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Space-aligned columns.
+    Terminal,
+    /// A GitHub-flavoured Markdown table.
+    Markdown,
+}
+
+/// `MIN` and `MAX` of `ty`, widened so every type fits.
+pub fn min_max(ty: IntType) -> (i128, u128) {
+    match ty {
+        IntType::I8 => (i8::MIN.into(), i8::MAX as u128),
+        IntType::I16 => (i16::MIN.into(), i16::MAX as u128),
+        IntType::I32 => (i32::MIN.into(), i32::MAX as u128),
+        IntType::I64 => (i64::MIN.into(), i64::MAX as u128),
+        IntType::I128 => (i128::MIN, i128::MAX as u128),
+        IntType::Isize => (isize::MIN as i128, isize::MAX as u128),
+        IntType::U8 => (0, u8::MAX.into()),
+        IntType::U16 => (0, u16::MAX.into()),
+        IntType::U32 => (0, u32::MAX.into()),
+        IntType::U64 => (0, u64::MAX.into()),
+        IntType::U128 => (0, u128::MAX),
+        IntType::Usize => (0, usize::MAX as u128),
+    }
+}
+
+/// The number of bits `ty` uses, worked out from its `MAX`: all ones for an
+/// unsigned type, all ones but the sign bit for a signed one.
+pub fn bits_from_max(ty: IntType) -> u32 {
+    let ones = min_max(ty).1.count_ones();
+    if ty.is_signed() {
+        ones + 1
+    } else {
+        ones
+    }
+}
+
+/// `MIN to MAX`, e.g. `-128 to 127`.
+pub fn range_text(ty: IntType) -> String {
+    let (min, max) = min_max(ty);
+    format!("{min} to {max}")
+}
+
+/// `ty`'s range as the lesson writes it, in powers of two: `-(2⁷) to 2⁷ - 1`
+/// for i8, `0 to 2⁸ - 1` for u8.
+pub fn formula(ty: IntType) -> String {
+    let n = bits_from_max(ty);
+    if ty.is_signed() {
+        let power = format!("2{}", superscript(&(n - 1).to_string()));
+        format!("-({power}) to {power} - 1")
+    } else {
+        format!("0 to 2{} - 1", superscript(&n.to_string()))
+    }
+}
+
+/// Digits, `n` and `-` as Unicode superscripts.
+pub fn superscript(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            '9' => '⁹',
+            'n' => 'ⁿ',
+            '-' => '⁻',
+            other => other,
+        })
+        .collect()
+}
+
+/// The "Length" column: `8-bit`, ..., or `arch (64-bit)` for isize/usize.
+fn length(signed: IntType) -> String {
+    match signed {
+        IntType::Isize => format!("arch ({}-bit)", bits_from_max(signed)),
+        _ => format!("{}-bit", bits_from_max(signed)),
+    }
+}
+
+/// (signed, unsigned) pairs in the lesson's row order.
+fn rows() -> impl Iterator<Item = (IntType, IntType)> {
+    let (signed, unsigned) = IntType::ALL.split_at(6);
+    signed.iter().copied().zip(unsigned.iter().copied())
+}
+
+/// The Length / Signed / Unsigned table with every type's range.
+pub fn table(format: Format) -> String {
+    let header = ["Length", "Signed", "Range", "Unsigned", "Range"];
+    let rows: Vec<[String; 5]> = rows()
+        .map(|(signed, unsigned)| {
+            [length(signed), signed.to_string(), range_text(signed), unsigned.to_string(), range_text(unsigned)]
+        })
+        .collect();
+
+    let mut out = String::new();
+    match format {
+        Format::Terminal => {
+            let mut widths = header.map(str::len);
+            for row in &rows {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+            let mut line = |cells: &[&str]| {
+                let padded: Vec<String> = cells.iter().zip(widths).map(|(cell, w)| format!("{cell:w$}")).collect();
+                writeln!(out, "{}", padded.join("  ").trim_end()).unwrap();
+            };
+            line(&header);
+            for row in &rows {
+                line(&row.each_ref().map(String::as_str));
+            }
+        }
+        Format::Markdown => {
+            writeln!(out, "| {} |", header.join(" | ")).unwrap();
+            writeln!(out, "|{}", "---|".repeat(header.len())).unwrap();
+            for [length, signed, signed_range, unsigned, unsigned_range] in &rows {
+                writeln!(out, "| {length} | `{signed}` | {signed_range} | `{unsigned}` | {unsigned_range} |").unwrap();
+            }
+        }
+    }
+    out
+}
+
+/// The paragraph and table the data-types lesson should carry, one comment
+/// line per line, built from the same constants as `table`.
+pub fn lesson_notes() -> String {
+    let (i8_min, i8_max) = min_max(IntType::I8);
+    let (_, u8_max) = min_max(IntType::U8);
+    let mut out = String::new();
+    writeln!(
+        out,
+        "Each signed variant can store numbers from -(2{n1}) to 2{n1} - 1 inclusive, where n is the number of bits that variant uses.",
+        n1 = superscript("n-1"),
+    )
+    .unwrap();
+    writeln!(out, "So an i8 can store numbers from {}, which equals {i8_min} to {i8_max}.", formula(IntType::I8))
+        .unwrap();
+    writeln!(
+        out,
+        "Unsigned variants can store numbers from 0 to 2{n} - 1, so a u8 can store numbers from {u8}, which equals 0 to {u8_max}.",
+        n = superscript("n"),
+        u8 = formula(IntType::U8),
+    )
+    .unwrap();
+    writeln!(out, "Length\tSigned\tUnsigned").unwrap();
+    for (signed, unsigned) in rows() {
+        let length = if signed == IntType::Isize { "arch".to_string() } else { length(signed) };
+        writeln!(out, "{length}\t{signed}\t{unsigned}").unwrap();
+    }
+    out
+}
@@ -0,0 +1,268 @@
+// The evaluator runs a program the type checker accepted, so it doesn't
+// re-check types: a mismatch here is a bug in the checker and panics. `break`
+// and `continue` travel up the Rust call stack as `Err(Unwind::...)` until
+// the loop they belong to catches them.
+//
+// Arithmetic overflows and division by zero panic the way a debug build of
+// the real program would. Loops get a budget of `FUEL` iterations in total
+// so that `loop {}` stops instead of hanging.
+
+use std::collections::HashMap;
+
+use super::parser::{BinOp, Block, Expr, Ident, Stmt, UnOp};
+use super::{Error, Value, FUEL};
+use crate::borrowck::Span;
+
+enum Unwind {
+    Break { label: Option<String>, value: Value },
+    Continue { label: Option<String> },
+    Error(Error),
+}
+
+impl From<Error> for Unwind {
+    fn from(error: Error) -> Self {
+        Unwind::Error(error)
+    }
+}
+
+/// Evaluates a checked program, appending whatever it prints to `output`.
+pub fn run(program: &Block, output: &mut Vec<String>) -> Result<Value, Error> {
+    let mut machine = Machine { scopes: Vec::new(), output, fuel: FUEL };
+    match machine.block(program) {
+        Ok(value) => Ok(value),
+        Err(Unwind::Error(error)) => Err(error),
+        Err(Unwind::Break { .. } | Unwind::Continue { .. }) => {
+            unreachable!("the checker rejects break and continue outside loops")
+        }
+    }
+}
+
+struct Machine<'o> {
+    scopes: Vec<HashMap<String, Value>>,
+    output: &'o mut Vec<String>,
+    fuel: usize,
+}
+
+/// Whether an unwinding `break` or `continue` with `label` is for the loop
+/// labelled `own`: unlabelled ones are for the innermost loop.
+fn targets(label: &Option<String>, own: Option<&Ident>) -> bool {
+    match label {
+        None => true,
+        Some(label) => own.is_some_and(|own| own.name == *label),
+    }
+}
+
+fn panic(message: &str, span: Span) -> Unwind {
+    Unwind::Error(Error::Panic { message: message.to_string(), span })
+}
+
+impl Machine<'_> {
+    fn lookup(&mut self, name: &str) -> &mut Value {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+            .unwrap_or_else(|| unreachable!("the checker rejects unknown name `{name}`"))
+    }
+
+    fn block(&mut self, block: &Block) -> Result<Value, Unwind> {
+        self.scopes.push(HashMap::new());
+        let result = self.block_body(block);
+        self.scopes.pop();
+        result
+    }
+
+    fn block_body(&mut self, block: &Block) -> Result<Value, Unwind> {
+        for stmt in &block.stmts {
+            self.stmt(stmt)?;
+        }
+        match &block.tail {
+            Some(tail) => self.expr(tail),
+            None => Ok(Value::Unit),
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) -> Result<(), Unwind> {
+        match stmt {
+            Stmt::Let { name, init, .. } => {
+                let value = self.expr(init)?;
+                self.scopes.last_mut().expect("let outside any scope").insert(name.name.clone(), value);
+            }
+            Stmt::Assign { target, op, value } => {
+                let value = self.expr(value)?;
+                let value = match op {
+                    Some(op) => {
+                        let current = self.lookup(&target.name).clone();
+                        self.binary(*op, current, value, target.span)?
+                    }
+                    None => value,
+                };
+                *self.lookup(&target.name) = value;
+            }
+            Stmt::Expr { expr, .. } => {
+                self.expr(expr)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Result<Value, Unwind> {
+        match expr {
+            Expr::Int(value, _) => Ok(Value::Int(*value as i32)),
+            Expr::Unit(_) => Ok(Value::Unit),
+            Expr::Bool(value, _) => Ok(Value::Bool(*value)),
+            Expr::Str(text, _) => Ok(Value::Str(text.clone())),
+            Expr::Var(ident) => Ok(self.lookup(&ident.name).clone()),
+            Expr::Unary { op, expr: operand, span } => {
+                if let (UnOp::Neg, Expr::Int(value, _)) = (op, &**operand) {
+                    // `-2147483648` is a literal, not a negation that overflows.
+                    return Ok(Value::Int(-value as i32));
+                }
+                match (op, self.expr(operand)?) {
+                    (UnOp::Neg, Value::Int(n)) => {
+                        n.checked_neg().map(Value::Int).ok_or_else(|| panic("attempt to negate with overflow", *span))
+                    }
+                    (UnOp::Not, Value::Int(n)) => Ok(Value::Int(!n)),
+                    (UnOp::Not, Value::Bool(b)) => Ok(Value::Bool(!b)),
+                    (op, value) => unreachable!("the checker rejects {op:?} on {value:?}"),
+                }
+            }
+            Expr::Binary { op: BinOp::And, lhs, rhs, .. } => {
+                // `&&` and `||` only evaluate the right side when they need to.
+                if self.expr(lhs)? == Value::Bool(false) {
+                    return Ok(Value::Bool(false));
+                }
+                self.expr(rhs)
+            }
+            Expr::Binary { op: BinOp::Or, lhs, rhs, .. } => {
+                if self.expr(lhs)? == Value::Bool(true) {
+                    return Ok(Value::Bool(true));
+                }
+                self.expr(rhs)
+            }
+            Expr::Binary { op, lhs, rhs, span } => {
+                let left = self.expr(lhs)?;
+                let right = self.expr(rhs)?;
+                self.binary(*op, left, right, *span)
+            }
+            Expr::Block(block) => self.block(block),
+            Expr::If { cond, then, else_, .. } => {
+                if self.expr(cond)? == Value::Bool(true) {
+                    self.block(then)
+                } else if let Some(else_) = else_ {
+                    self.expr(else_)
+                } else {
+                    Ok(Value::Unit)
+                }
+            }
+            Expr::Loop { label, body, span } => loop {
+                self.burn(*span)?;
+                match self.block(body) {
+                    Ok(_) => {}
+                    Err(Unwind::Break { label: l, value }) if targets(&l, label.as_ref()) => return Ok(value),
+                    Err(Unwind::Continue { label: l }) if targets(&l, label.as_ref()) => {}
+                    Err(other) => return Err(other),
+                }
+            },
+            Expr::While { label, cond, body, span } => {
+                while self.expr(cond)? == Value::Bool(true) {
+                    self.burn(*span)?;
+                    match self.block(body) {
+                        Ok(_) => {}
+                        Err(Unwind::Break { label: l, .. }) if targets(&l, label.as_ref()) => break,
+                        Err(Unwind::Continue { label: l }) if targets(&l, label.as_ref()) => {}
+                        Err(other) => return Err(other),
+                    }
+                }
+                Ok(Value::Unit)
+            }
+            Expr::For { label, var, iter, body, span } => {
+                let Value::Range { start, end, inclusive, rev } = self.expr(iter)? else {
+                    unreachable!("the checker only lets `for` loop over ranges");
+                };
+                let end = if inclusive { i64::from(end) + 1 } else { i64::from(end) };
+                let numbers: Box<dyn Iterator<Item = i64>> =
+                    if rev { Box::new((i64::from(start)..end).rev()) } else { Box::new(i64::from(start)..end) };
+                for number in numbers {
+                    self.burn(*span)?;
+                    self.scopes.push(HashMap::from([(var.name.clone(), Value::Int(number as i32))]));
+                    let result = self.block(body);
+                    self.scopes.pop();
+                    match result {
+                        Ok(_) => {}
+                        Err(Unwind::Break { label: l, .. }) if targets(&l, label.as_ref()) => break,
+                        Err(Unwind::Continue { label: l }) if targets(&l, label.as_ref()) => {}
+                        Err(other) => return Err(other),
+                    }
+                }
+                Ok(Value::Unit)
+            }
+            Expr::Range { start, end, inclusive, rev, .. } => {
+                let (Value::Int(start), Value::Int(end)) = (self.expr(start)?, self.expr(end)?) else {
+                    unreachable!("the checker only allows `i32` range bounds");
+                };
+                Ok(Value::Range { start, end, inclusive: *inclusive, rev: *rev })
+            }
+            Expr::Break { label, value, .. } => {
+                let value = match value {
+                    Some(value) => self.expr(value)?,
+                    None => Value::Unit,
+                };
+                Err(Unwind::Break { label: label.as_ref().map(|l| l.name.clone()), value })
+            }
+            Expr::Continue { label, .. } => Err(Unwind::Continue { label: label.as_ref().map(|l| l.name.clone()) }),
+            Expr::Call { args, .. } => {
+                let mut line = String::new();
+                for arg in args {
+                    line.push_str(&self.expr(arg)?.to_string());
+                }
+                self.output.push(line);
+                Ok(Value::Unit)
+            }
+        }
+    }
+
+    fn binary(&self, op: BinOp, left: Value, right: Value, span: Span) -> Result<Value, Unwind> {
+        let (Value::Int(a), Value::Int(b)) = (&left, &right) else {
+            return Ok(Value::Bool(match op {
+                BinOp::Eq => left == right,
+                BinOp::Ne => left != right,
+                BinOp::Lt => left < right,
+                BinOp::Le => left <= right,
+                BinOp::Gt => left > right,
+                BinOp::Ge => left >= right,
+                _ => unreachable!("the checker rejects {op:?} on {left:?} and {right:?}"),
+            }));
+        };
+        let (a, b) = (*a, *b);
+        let arithmetic =
+            |result: Option<i32>, message: &str| result.map(Value::Int).ok_or_else(|| panic(message, span));
+        match op {
+            BinOp::Add => arithmetic(a.checked_add(b), "attempt to add with overflow"),
+            BinOp::Sub => arithmetic(a.checked_sub(b), "attempt to subtract with overflow"),
+            BinOp::Mul => arithmetic(a.checked_mul(b), "attempt to multiply with overflow"),
+            BinOp::Div if b == 0 => Err(panic("attempt to divide by zero", span)),
+            BinOp::Div => arithmetic(a.checked_div(b), "attempt to divide with overflow"),
+            BinOp::Rem if b == 0 => Err(panic("attempt to calculate the remainder with a divisor of zero", span)),
+            BinOp::Rem => arithmetic(a.checked_rem(b), "attempt to calculate the remainder with overflow"),
+            BinOp::Eq => Ok(Value::Bool(a == b)),
+            BinOp::Ne => Ok(Value::Bool(a != b)),
+            BinOp::Lt => Ok(Value::Bool(a < b)),
+            BinOp::Le => Ok(Value::Bool(a <= b)),
+            BinOp::Gt => Ok(Value::Bool(a > b)),
+            BinOp::Ge => Ok(Value::Bool(a >= b)),
+            BinOp::And | BinOp::Or => unreachable!("`&&` and `||` on `i32`"),
+        }
+    }
+
+    /// Spends one loop iteration of fuel.
+    fn burn(&mut self, span: Span) -> Result<(), Unwind> {
+        match self.fuel.checked_sub(1) {
+            Some(left) => {
+                self.fuel = left;
+                Ok(())
+            }
+            None => Err(Unwind::Error(Error::OutOfFuel { span })),
+        }
+    }
+}
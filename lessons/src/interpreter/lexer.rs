@@ -0,0 +1,117 @@
+// Tokens of the expression language. Keywords (`if`, `loop`, `true`, ...)
+// come out as identifiers; the parser tells them apart.
+
+use crate::borrowck::{Diagnostic, Span};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident(String),
+    Int(i64),
+    Str(String),
+    /// A loop label such as `'counting_up`, quote included.
+    Label(String),
+    Punct(&'static str),
+    Eof,
+}
+
+#[derive(Clone, Debug)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+// Longest first, so `..=` isn't read as `..` then `=`.
+const PUNCTS: &[&str] = &[
+    "..=", "..", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=", "*=", "/=", "%=", "(", ")", "{", "}", ";", ",", "=",
+    "&", "+", "-", "*", "/", "%", "<", ">", "!", ":", ".",
+];
+
+pub fn tokenize(source: &str) -> Result<Vec<Token>, Diagnostic> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            i += 1;
+        } else if source[i..].starts_with("//") {
+            i = source[i..].find('\n').map_or(bytes.len(), |n| i + n);
+        } else if c.is_ascii_alphabetic() || c == b'_' || c == b'\'' {
+            let start = i;
+            i += 1;
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            let text = source[start..i].to_string();
+            let span = Span::new(start, i);
+            let kind = if c != b'\'' {
+                TokenKind::Ident(text)
+            } else if text.len() > 1 {
+                TokenKind::Label(text)
+            } else {
+                return Err(Diagnostic::error(
+                    None,
+                    "char literals aren't supported; labels look like `'name`",
+                    span,
+                    "",
+                ));
+            };
+            tokens.push(Token { kind, span });
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'_') {
+                i += 1;
+            }
+            let span = Span::new(start, i);
+            let value = source[start..i]
+                .replace('_', "")
+                .parse()
+                .map_err(|_| Diagnostic::error(None, "integer literal is too large", span, ""))?;
+            tokens.push(Token { kind: TokenKind::Int(value), span });
+        } else if c == b'"' {
+            let (text, end) = string(source, i)?;
+            tokens.push(Token { kind: TokenKind::Str(text), span: Span::new(i, end) });
+            i = end;
+        } else if let Some(punct) = PUNCTS.iter().find(|p| source[i..].starts_with(**p)) {
+            tokens.push(Token { kind: TokenKind::Punct(punct), span: Span::new(i, i + punct.len()) });
+            i += punct.len();
+        } else {
+            let ch = source[i..].chars().next().unwrap();
+            let span = Span::new(i, i + ch.len_utf8());
+            return Err(Diagnostic::error(None, format!("unknown start of token: {ch}"), span, ""));
+        }
+    }
+    tokens.push(Token { kind: TokenKind::Eof, span: Span::new(bytes.len(), bytes.len()) });
+    Ok(tokens)
+}
+
+/// The string literal whose opening quote is at `start`, with its escapes
+/// (`\n`, `\r`, `\t`, `\0`, `\\`, `\'` and `\"`) resolved, and the index
+/// just past its closing quote.
+fn string(source: &str, start: usize) -> Result<(String, usize), Diagnostic> {
+    let mut text = String::new();
+    let mut chars = source[start + 1..].char_indices().map(|(n, ch)| (start + 1 + n, ch));
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '"' => return Ok((text, i + 1)),
+            '\\' => {
+                let escaped = match chars.next() {
+                    Some((_, 'n')) => '\n',
+                    Some((_, 'r')) => '\r',
+                    Some((_, 't')) => '\t',
+                    Some((_, '0')) => '\0',
+                    Some((_, ch @ ('\\' | '\'' | '"'))) => ch,
+                    Some((j, other)) => {
+                        let span = Span::new(i, j + other.len_utf8());
+                        return Err(Diagnostic::error(None, format!("unknown character escape: `{other}`"), span, "")
+                            .with_note("help: the escapes here are \\n, \\r, \\t, \\0, \\\\, \\' and \\\""));
+                    }
+                    None => break,
+                };
+                text.push(escaped);
+            }
+            _ => text.push(ch),
+        }
+    }
+    Err(Diagnostic::error(None, "unterminated double quote string", Span::new(start, source.len()), ""))
+}
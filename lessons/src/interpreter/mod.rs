@@ -0,0 +1,236 @@
+// Expression interpreter
+// The control-flow lesson's point is that `if`, `loop` and blocks are
+// expressions: they have values, and those values have types the compiler
+// checks. This module runs a tiny Rust-like language built around exactly
+// that, so programs like
+//
+//     let mut counter = 0;
+//     let result = loop {
+//         counter += 1;
+//         if counter == 10 {
+//             break counter * 2;
+//         }
+//     };
+//     result
+//
+// can be tried out and broken on purpose. The language has
+//   - types `i32`, `bool`, `&str` and `()`, plus ranges for `for`,
+//   - `let x = e;`, `let mut x: T = e;`, `x = e;` and `x += e;` (also `-=`,
+//     `*=`, `/=`, `%=`),
+//   - integers, `true`, `false`, `()`, "strings" (with the escapes `\n`,
+//     `\r`, `\t`, `\0`, `\\`, `\'` and `\"`), `- ! * / % + - == != < <= > >=
+//     && ||` with Rust's precedence, and `{ ... }` blocks with an optional tail,
+//   - `if`/`else if`/`else`, `loop`, `while`, `for x in a..b` (or `a..=b`, or
+//     `(a..b).rev()`), labels like `'outer: loop`, `break`, `break value`,
+//     `break 'label value` and `continue`,
+//   - `print(a, b, ...)`, which prints its arguments run together on one line.
+//
+// A program is a block without the braces; its value is the final expression.
+// As in rustc, a statement that starts with a block, `if` or a loop ends with
+// it: `{ 1 } - 1` is the block `{ 1 }` (an E0308, since it isn't `()`)
+// followed by `-1`; `({ 1 }) - 1` is the subtraction.
+// Programs are type checked first, and rejected the way rustc would reject
+// them: `if number {` on an `i32` is E0308, so are `if` arms of different
+// types, `break value` from a `while` is E0571, and so on. A program that
+// passes then runs, and can still panic on overflow or division by zero.
+// Expressions nest at most `MAX_NESTING` levels deep, and programs run on a
+// thread with a stack sized for that, so a pathological program gets an
+// error rather than overflowing the stack.
+
+mod eval;
+mod lexer;
+mod parser;
+mod types;
+
+use std::fmt;
+use std::panic;
+use std::thread;
+
+use crate::borrowck::{Diagnostic, Span};
+
+pub use parser::MAX_NESTING;
+
+/// Loop iterations a program may run in total before it's stopped.
+pub const FUEL: usize = 1_000_000;
+
+/// Stack for the thread programs are parsed, checked and run on. A debug
+/// build uses up to about 40 KiB per nesting level, so `MAX_NESTING` levels
+/// would not fit in a 2 MiB test thread; this leaves plenty of room.
+const STACK_SIZE: usize = 16 * 1024 * 1024;
+
+/// The types a program's expressions can have.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Type {
+    I32,
+    Bool,
+    Str,
+    Unit,
+    /// `!`, the type of expressions that never produce a value, like `break`.
+    Never,
+    Range {
+        inclusive: bool,
+        rev: bool,
+    },
+}
+
+impl Type {
+    /// The type a `let` annotation names, if it's one of ours.
+    fn parse(text: &str) -> Option<Type> {
+        match text {
+            "i32" => Some(Type::I32),
+            "bool" => Some(Type::Bool),
+            "&str" => Some(Type::Str),
+            "()" => Some(Type::Unit),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::I32 => f.write_str("i32"),
+            Type::Bool => f.write_str("bool"),
+            Type::Str => f.write_str("&str"),
+            Type::Unit => f.write_str("()"),
+            Type::Never => f.write_str("!"),
+            Type::Range { inclusive, rev } => {
+                let range = if *inclusive { "RangeInclusive<i32>" } else { "Range<i32>" };
+                if *rev {
+                    write!(f, "Rev<{range}>")
+                } else {
+                    f.write_str(range)
+                }
+            }
+        }
+    }
+}
+
+/// A program's value.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Value {
+    Int(i32),
+    Bool(bool),
+    Str(String),
+    Unit,
+    Range { start: i32, end: i32, inclusive: bool, rev: bool },
+}
+
+impl Value {
+    /// The value as it would be written in a program: strings quoted.
+    pub fn literal(&self) -> String {
+        match self {
+            Value::Str(text) => format!("{text:?}"),
+            other => other.to_string(),
+        }
+    }
+}
+
+/// What `print` shows: strings without quotes, like `{}` in `println!`.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{n}"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Str(text) => f.write_str(text),
+            Value::Unit => f.write_str("()"),
+            Value::Range { start, end, inclusive, rev } => {
+                let dots = if *inclusive { "..=" } else { ".." };
+                if *rev {
+                    write!(f, "({start}{dots}{end}).rev()")
+                } else {
+                    write!(f, "{start}{dots}{end}")
+                }
+            }
+        }
+    }
+}
+
+/// Why a program didn't produce a value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Rejected before running: a syntax or type error.
+    Compile(Diagnostic),
+    /// Panicked while running, e.g. `attempt to add with overflow`.
+    Panic { message: String, span: Span },
+    /// Ran more than `FUEL` loop iterations; `span` is the loop it was in.
+    OutOfFuel { span: Span },
+}
+
+impl Error {
+    /// The error as rustc or the running program would print it.
+    pub fn render(&self, source: &str) -> String {
+        match self {
+            Error::Compile(diagnostic) => diagnostic.render(source),
+            Error::Panic { message, span } => {
+                let (line, col) = span.line_col(source);
+                format!("thread 'main' panicked at {line}:{col}:\n{message}\n")
+            }
+            Error::OutOfFuel { span } => {
+                let (line, col) = span.line_col(source);
+                format!("stopped after {FUEL} loop iterations in the loop at {line}:{col}; is it infinite?\n")
+            }
+        }
+    }
+}
+
+/// A program that ran to the end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub value: Value,
+    /// The type checker's type for the program's value.
+    pub ty: Type,
+    /// One entry per `print` call.
+    pub output: Vec<String>,
+}
+
+/// Parses and type checks `source`, returning the type of its value.
+pub fn check(source: &str) -> Result<Type, Diagnostic> {
+    on_own_stack(|| {
+        let tokens = lexer::tokenize(source)?;
+        let program = parser::parse(&tokens)?;
+        types::check(&program)
+    })
+}
+
+/// Checks and runs `source`. On a panic, `output` holds what was printed
+/// before it.
+pub fn run_with_output(source: &str, output: &mut Vec<String>) -> Result<Outcome, Error> {
+    on_own_stack(|| {
+        let tokens = lexer::tokenize(source).map_err(Error::Compile)?;
+        let program = parser::parse(&tokens).map_err(Error::Compile)?;
+        let ty = types::check(&program).map_err(Error::Compile)?;
+        let value = eval::run(&program, output)?;
+        Ok(Outcome { value, ty, output: output.clone() })
+    })
+}
+
+/// Runs `f` on a thread with a `STACK_SIZE` stack and waits for it.
+fn on_own_stack<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    thread::scope(|scope| {
+        let worker = thread::Builder::new()
+            .name("interpreter".to_string())
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(scope, f)
+            .expect("spawn the interpreter thread");
+        worker.join().unwrap_or_else(|payload| panic::resume_unwind(payload))
+    })
+}
+
+/// Checks and runs `source`.
+pub fn run(source: &str) -> Result<Outcome, Error> {
+    run_with_output(source, &mut Vec::new())
+}
+
+/// What running `source` looks like: the printed lines, then either
+/// `result: TYPE = VALUE` or the error.
+pub fn report(source: &str) -> String {
+    let mut output = Vec::new();
+    let result = run_with_output(source, &mut output);
+    let mut out: String = output.iter().map(|line| format!("{line}\n")).collect();
+    match result {
+        Ok(outcome) => out.push_str(&format!("result: {} = {}\n", outcome.ty, outcome.value.literal())),
+        Err(error) => out.push_str(&error.render(source)),
+    }
+    out
+}
@@ -0,0 +1,666 @@
+// Syntax tree and recursive-descent parser for the expression language.
+// Operator precedence follows Rust's: unary `-` and `!`, then `* / %`,
+// `+ -`, comparisons (which don't chain), `&&`, `||`, and ranges lowest.
+
+use super::lexer::{Token, TokenKind};
+use crate::borrowck::{Diagnostic, Span};
+
+#[derive(Clone, Debug)]
+pub struct Ident {
+    pub name: String,
+    pub span: Span,
+}
+
+/// `{ stmts; tail }`. A whole program is a block without the braces.
+#[derive(Debug)]
+pub struct Block {
+    pub stmts: Vec<Stmt>,
+    pub tail: Option<Box<Expr>>,
+    pub span: Span,
+}
+
+impl Block {
+    /// Where the block's value comes from: its tail, or the closing `}`
+    /// when it has none and so is `()`.
+    pub fn value_span(&self) -> Span {
+        self.tail.as_ref().map_or(Span::new(self.span.end.saturating_sub(1), self.span.end), |tail| tail.span())
+    }
+}
+
+#[derive(Debug)]
+pub enum Stmt {
+    Let {
+        name: Ident,
+        mutable: bool,
+        ty: Option<(String, Span)>,
+        init: Expr,
+    },
+    /// `x = value;`, or `x op= value;` when `op` is set.
+    Assign {
+        target: Ident,
+        op: Option<BinOp>,
+        value: Expr,
+    },
+    /// An expression statement; `semi` is false for a block-like expression
+    /// (`if`, `loop`, ...) written without a `;`.
+    Expr {
+        expr: Expr,
+        semi: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnOp {
+    Neg,
+    Not,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+impl BinOp {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            BinOp::Add => "+",
+            BinOp::Sub => "-",
+            BinOp::Mul => "*",
+            BinOp::Div => "/",
+            BinOp::Rem => "%",
+            BinOp::Eq => "==",
+            BinOp::Ne => "!=",
+            BinOp::Lt => "<",
+            BinOp::Le => "<=",
+            BinOp::Gt => ">",
+            BinOp::Ge => ">=",
+            BinOp::And => "&&",
+            BinOp::Or => "||",
+        }
+    }
+
+    fn from_assign(punct: &str) -> Option<BinOp> {
+        match punct {
+            "+=" => Some(BinOp::Add),
+            "-=" => Some(BinOp::Sub),
+            "*=" => Some(BinOp::Mul),
+            "/=" => Some(BinOp::Div),
+            "%=" => Some(BinOp::Rem),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Expr {
+    /// `()`.
+    Unit(Span),
+    Int(i64, Span),
+    Bool(bool, Span),
+    Str(String, Span),
+    Var(Ident),
+    Unary {
+        op: UnOp,
+        expr: Box<Expr>,
+        span: Span,
+    },
+    Binary {
+        op: BinOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+        span: Span,
+    },
+    Block(Block),
+    /// `else_` is a `Block` or, for `else if`, another `If`.
+    If {
+        cond: Box<Expr>,
+        then: Block,
+        else_: Option<Box<Expr>>,
+        span: Span,
+    },
+    Loop {
+        label: Option<Ident>,
+        body: Block,
+        span: Span,
+    },
+    While {
+        label: Option<Ident>,
+        cond: Box<Expr>,
+        body: Block,
+        span: Span,
+    },
+    For {
+        label: Option<Ident>,
+        var: Ident,
+        iter: Box<Expr>,
+        body: Block,
+        span: Span,
+    },
+    /// `start..end` or `start..=end`, reversed by a trailing `.rev()`.
+    Range {
+        start: Box<Expr>,
+        end: Box<Expr>,
+        inclusive: bool,
+        rev: bool,
+        span: Span,
+    },
+    Break {
+        label: Option<Ident>,
+        value: Option<Box<Expr>>,
+        span: Span,
+    },
+    Continue {
+        label: Option<Ident>,
+        span: Span,
+    },
+    Call {
+        name: Ident,
+        args: Vec<Expr>,
+        span: Span,
+    },
+}
+
+impl Expr {
+    pub fn span(&self) -> Span {
+        match self {
+            Expr::Unit(span) | Expr::Int(_, span) | Expr::Bool(_, span) | Expr::Str(_, span) => *span,
+            Expr::Var(ident) => ident.span,
+            Expr::Block(block) => block.span,
+            Expr::Unary { span, .. }
+            | Expr::Binary { span, .. }
+            | Expr::If { span, .. }
+            | Expr::Loop { span, .. }
+            | Expr::While { span, .. }
+            | Expr::For { span, .. }
+            | Expr::Range { span, .. }
+            | Expr::Break { span, .. }
+            | Expr::Continue { span, .. }
+            | Expr::Call { span, .. } => *span,
+        }
+    }
+
+    /// Block-like expressions can stand as statements without a `;`.
+    fn is_block_like(&self) -> bool {
+        matches!(self, Expr::Block(_) | Expr::If { .. } | Expr::Loop { .. } | Expr::While { .. } | Expr::For { .. })
+    }
+}
+
+/// How deeply expressions may nest inside a statement, counting blocks,
+/// parentheses, unary operators, `else if` and each operator in a chain. The
+/// parser, the type checker and the evaluator all recurse once per level, so
+/// without a limit a long enough `((((...` would overflow the stack instead
+/// of getting an error.
+pub const MAX_NESTING: usize = 64;
+
+const KEYWORDS: &[&str] =
+    &["let", "mut", "if", "else", "loop", "while", "for", "in", "break", "continue", "true", "false"];
+
+/// Parses a whole program: statements and an optional final expression.
+pub fn parse(tokens: &[Token]) -> Result<Block, Diagnostic> {
+    let mut parser = Parser { tokens, pos: 0, depth: 0 };
+    let start = parser.span();
+    let (stmts, tail) = parser.statements(true)?;
+    let end = parser.span();
+    Ok(Block { stmts, tail, span: start.to(end) })
+}
+
+struct Parser<'t> {
+    tokens: &'t [Token],
+    pos: usize,
+    /// How many nesting levels are in progress; see `MAX_NESTING`.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> &TokenKind {
+        &self.tokens[self.pos].kind
+    }
+
+    fn peek_at(&self, offset: usize) -> &TokenKind {
+        let last = self.tokens.len() - 1;
+        &self.tokens[(self.pos + offset).min(last)].kind
+    }
+
+    fn span(&self) -> Span {
+        self.tokens[self.pos].span
+    }
+
+    /// The span of the last token consumed.
+    fn previous(&self) -> Span {
+        self.tokens[self.pos.saturating_sub(1)].span
+    }
+
+    fn advance(&mut self) -> &Token {
+        let token = &self.tokens[self.pos];
+        if token.kind != TokenKind::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn is_punct(&self, punct: &str) -> bool {
+        matches!(self.peek(), TokenKind::Punct(p) if *p == punct)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), TokenKind::Ident(name) if name == keyword)
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        let found = self.is_punct(punct);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn unexpected(&self, expected: &str) -> Diagnostic {
+        let found = match self.peek() {
+            TokenKind::Ident(name) => format!("`{name}`"),
+            TokenKind::Int(value) => format!("`{value}`"),
+            TokenKind::Str(text) => format!("`{text:?}`"),
+            TokenKind::Label(label) => format!("`{label}`"),
+            TokenKind::Punct(p) => format!("`{p}`"),
+            TokenKind::Eof => "end of input".to_string(),
+        };
+        Diagnostic::error(None, format!("expected {expected}, found {found}"), self.span(), "")
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> Result<Span, Diagnostic> {
+        let span = self.span();
+        if self.eat_punct(punct) {
+            Ok(span)
+        } else {
+            Err(self.unexpected(&format!("`{punct}`")))
+        }
+    }
+
+    /// Runs `parse` one nesting level deeper, or fails past `MAX_NESTING`.
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, Diagnostic>) -> Result<T, Diagnostic> {
+        self.enter()?;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Goes one nesting level deeper; the caller comes back out.
+    fn enter(&mut self) -> Result<(), Diagnostic> {
+        // The statement's own expression is level 0.
+        if self.depth > MAX_NESTING {
+            return Err(Diagnostic::error(None, "expression nested too deeply", self.span(), "").with_note(format!(
+                "note: at most {MAX_NESTING} levels of blocks, parentheses and operators are allowed"
+            )));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn ident(&mut self) -> Result<Ident, Diagnostic> {
+        match self.peek().clone() {
+            TokenKind::Ident(name) if !KEYWORDS.contains(&name.as_str()) => {
+                let span = self.advance().span;
+                Ok(Ident { name, span })
+            }
+            _ => Err(self.unexpected("an identifier")),
+        }
+    }
+
+    fn label(&mut self) -> Option<Ident> {
+        match self.peek().clone() {
+            TokenKind::Label(name) => {
+                let span = self.advance().span;
+                Some(Ident { name, span })
+            }
+            _ => None,
+        }
+    }
+
+    /// Statements up to `}` (or the end of input at the top level), and the
+    /// final expression if it has no `;`. As in rustc, an expression statement
+    /// that starts with a block-like expression ends after it, so `{ 1 } - 1`
+    /// is the statement `{ 1 }` followed by `-1`, not a subtraction.
+    fn statements(&mut self, top_level: bool) -> Result<(Vec<Stmt>, Option<Box<Expr>>), Diagnostic> {
+        let mut stmts = Vec::new();
+        let mut tail = None;
+        let at_end = |p: &Self| if top_level { p.peek() == &TokenKind::Eof } else { p.is_punct("}") };
+        while !at_end(self) {
+            if self.is_keyword("let") {
+                stmts.push(self.let_stmt()?);
+                continue;
+            }
+            if let Some(assign) = self.assign_stmt()? {
+                stmts.push(assign);
+                continue;
+            }
+            let expr = if self.starts_block_like() { self.nested(Self::postfix)? } else { self.expr()? };
+            if self.eat_punct(";") {
+                stmts.push(Stmt::Expr { expr, semi: true });
+            } else if at_end(self) {
+                tail = Some(Box::new(expr));
+            } else if expr.is_block_like() {
+                stmts.push(Stmt::Expr { expr, semi: false });
+            } else {
+                return Err(self.unexpected(if top_level { "`;` or end of input" } else { "`;` or `}`" }));
+            }
+        }
+        Ok((stmts, tail))
+    }
+
+    fn block(&mut self) -> Result<Block, Diagnostic> {
+        let open = self.expect_punct("{")?;
+        let (stmts, tail) = self.statements(false)?;
+        let close = self.expect_punct("}")?;
+        Ok(Block { stmts, tail, span: open.to(close) })
+    }
+
+    fn let_stmt(&mut self) -> Result<Stmt, Diagnostic> {
+        self.advance(); // let
+        let mutable = self.eat_keyword("mut");
+        let name = self.ident()?;
+        let ty = if self.eat_punct(":") {
+            let start = self.span();
+            let text = match self.peek().clone() {
+                TokenKind::Ident(name) => {
+                    self.advance();
+                    name
+                }
+                TokenKind::Punct("&") if matches!(self.peek_at(1), TokenKind::Ident(name) if name == "str") => {
+                    self.advance();
+                    self.advance();
+                    "&str".to_string()
+                }
+                TokenKind::Punct("(") if matches!(self.peek_at(1), TokenKind::Punct(")")) => {
+                    self.advance();
+                    self.advance();
+                    "()".to_string()
+                }
+                _ => return Err(self.unexpected("a type (`i32`, `bool`, `&str` or `()`)")),
+            };
+            Some((text, start.to(self.previous())))
+        } else {
+            None
+        };
+        if !self.eat_punct("=") {
+            return Err(self.unexpected("`=` (every `let` needs a value here)"));
+        }
+        let init = self.expr()?;
+        self.expect_punct(";")?;
+        Ok(Stmt::Let { name, mutable, ty, init })
+    }
+
+    /// `x = e;` or `x += e;`; anything else is left for `expr`.
+    fn assign_stmt(&mut self) -> Result<Option<Stmt>, Diagnostic> {
+        let op = match (self.peek(), self.peek_at(1)) {
+            (TokenKind::Ident(_), TokenKind::Punct("=")) => None,
+            (TokenKind::Ident(_), TokenKind::Punct(p)) if BinOp::from_assign(p).is_some() => BinOp::from_assign(p),
+            _ => return Ok(None),
+        };
+        let target = self.ident()?;
+        self.advance(); // = or op=
+        let value = self.expr()?;
+        self.expect_punct(";")?;
+        Ok(Some(Stmt::Assign { target, op, value }))
+    }
+
+    fn expr(&mut self) -> Result<Expr, Diagnostic> {
+        self.nested(Self::range)
+    }
+
+    fn range(&mut self) -> Result<Expr, Diagnostic> {
+        let start = self.or()?;
+        let inclusive = if self.eat_punct("..") {
+            false
+        } else if self.eat_punct("..=") {
+            true
+        } else {
+            return Ok(start);
+        };
+        let end = self.or()?;
+        let span = start.span().to(end.span());
+        Ok(Expr::Range { start: Box::new(start), end: Box::new(end), inclusive, rev: false, span })
+    }
+
+    fn binary_level(
+        &mut self,
+        ops: &[(&str, BinOp)],
+        next: fn(&mut Self) -> Result<Expr, Diagnostic>,
+        chains: bool,
+    ) -> Result<Expr, Diagnostic> {
+        let mut lhs = next(self)?;
+        // `a + b + c` is `(a + b) + c`: every operator puts the chain one level deeper.
+        let depth = self.depth;
+        loop {
+            let Some(&(_, op)) = ops.iter().find(|(punct, _)| self.is_punct(punct)) else {
+                self.depth = depth;
+                return Ok(lhs);
+            };
+            self.enter()?;
+            let op_span = self.advance().span;
+            let rhs = next(self)?;
+            let span = lhs.span().to(rhs.span());
+            lhs = Expr::Binary { op, lhs: Box::new(lhs), rhs: Box::new(rhs), span };
+            if !chains && ops.iter().any(|(punct, _)| self.is_punct(punct)) {
+                return Err(Diagnostic::error(None, "comparison operators cannot be chained", self.span(), "")
+                    .with_label(op_span, "")
+                    .with_note("help: split the comparison into two, joined by `&&`"));
+            }
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, Diagnostic> {
+        self.binary_level(&[("||", BinOp::Or)], Self::and, true)
+    }
+
+    fn and(&mut self) -> Result<Expr, Diagnostic> {
+        self.binary_level(&[("&&", BinOp::And)], Self::comparison, true)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, Diagnostic> {
+        let ops = [
+            ("==", BinOp::Eq),
+            ("!=", BinOp::Ne),
+            ("<=", BinOp::Le),
+            (">=", BinOp::Ge),
+            ("<", BinOp::Lt),
+            (">", BinOp::Gt),
+        ];
+        self.binary_level(&ops, Self::sum, false)
+    }
+
+    fn sum(&mut self) -> Result<Expr, Diagnostic> {
+        self.binary_level(&[("+", BinOp::Add), ("-", BinOp::Sub)], Self::product, true)
+    }
+
+    fn product(&mut self) -> Result<Expr, Diagnostic> {
+        self.binary_level(&[("*", BinOp::Mul), ("/", BinOp::Div), ("%", BinOp::Rem)], Self::unary, true)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Diagnostic> {
+        let start = self.span();
+        let op = if self.eat_punct("-") {
+            UnOp::Neg
+        } else if self.eat_punct("!") {
+            UnOp::Not
+        } else {
+            return self.postfix();
+        };
+        let expr = self.nested(Self::unary)?;
+        let span = start.to(expr.span());
+        Ok(Expr::Unary { op, expr: Box::new(expr), span })
+    }
+
+    /// A primary expression followed by `.rev()` calls.
+    fn postfix(&mut self) -> Result<Expr, Diagnostic> {
+        let mut expr = self.primary()?;
+        while self.eat_punct(".") {
+            match self.peek() {
+                TokenKind::Ident(name) if name == "rev" => {
+                    self.advance();
+                }
+                _ => return Err(self.unexpected("`rev` (the only method here)")),
+            }
+            self.expect_punct("(")?;
+            let close = self.expect_punct(")")?;
+            expr = match expr {
+                Expr::Range { start, end, inclusive, rev, span } => {
+                    Expr::Range { start, end, inclusive, rev: !rev, span: span.to(close) }
+                }
+                other => {
+                    return Err(Diagnostic::error(None, "`.rev()` only works on a range", other.span(), "")
+                        .with_note("help: wrap the range in parentheses: `(1..4).rev()`"))
+                }
+            };
+        }
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> Result<Expr, Diagnostic> {
+        let start = self.span();
+        if self.is_punct("{") {
+            return Ok(Expr::Block(self.block()?));
+        }
+        if self.eat_punct("(") {
+            if self.is_punct(")") {
+                let close = self.advance().span;
+                return Ok(Expr::Unit(start.to(close)));
+            }
+            let inner = self.expr()?;
+            self.expect_punct(")")?;
+            return Ok(inner);
+        }
+        if let Some(label) = self.label() {
+            self.expect_punct(":")?;
+            return self.labelled_loop(Some(label), start);
+        }
+        match self.peek().clone() {
+            TokenKind::Int(value) => {
+                self.advance();
+                Ok(Expr::Int(value, start))
+            }
+            TokenKind::Str(text) => {
+                self.advance();
+                Ok(Expr::Str(text, start))
+            }
+            TokenKind::Ident(name) => match name.as_str() {
+                "true" | "false" => {
+                    self.advance();
+                    Ok(Expr::Bool(name == "true", start))
+                }
+                "if" => self.if_expr(),
+                "loop" | "while" | "for" => self.labelled_loop(None, start),
+                "break" => {
+                    self.advance();
+                    let label = self.label();
+                    let value = if self.starts_expr() { Some(Box::new(self.expr()?)) } else { None };
+                    let end = value.as_ref().map_or(self.previous(), |v| v.span());
+                    Ok(Expr::Break { label, value, span: start.to(end) })
+                }
+                "continue" => {
+                    self.advance();
+                    let label = self.label();
+                    Ok(Expr::Continue { label, span: start.to(self.previous()) })
+                }
+                _ => {
+                    let name = self.ident()?;
+                    if !self.eat_punct("(") {
+                        return Ok(Expr::Var(name));
+                    }
+                    let mut args = Vec::new();
+                    while !self.is_punct(")") {
+                        args.push(self.expr()?);
+                        if !self.eat_punct(",") {
+                            break;
+                        }
+                    }
+                    let end = self.expect_punct(")")?;
+                    let span = name.span.to(end);
+                    Ok(Expr::Call { name, args, span })
+                }
+            },
+            _ => Err(self.unexpected("an expression")),
+        }
+    }
+
+    /// Whether the next token can begin an expression, i.e. whether a
+    /// `break` has a value.
+    fn starts_expr(&self) -> bool {
+        match self.peek() {
+            TokenKind::Int(_) | TokenKind::Str(_) => true,
+            TokenKind::Ident(name) => !matches!(name.as_str(), "else" | "in"),
+            TokenKind::Punct(p) => matches!(*p, "(" | "{" | "-" | "!"),
+            TokenKind::Label(_) | TokenKind::Eof => false,
+        }
+    }
+
+    /// Whether the next token begins a block-like expression: a block, `if`,
+    /// a loop or a label.
+    fn starts_block_like(&self) -> bool {
+        match self.peek() {
+            TokenKind::Punct(p) => *p == "{",
+            TokenKind::Ident(name) => matches!(name.as_str(), "if" | "loop" | "while" | "for"),
+            TokenKind::Label(_) => true,
+            TokenKind::Int(_) | TokenKind::Str(_) | TokenKind::Eof => false,
+        }
+    }
+
+    fn if_expr(&mut self) -> Result<Expr, Diagnostic> {
+        let start = self.advance().span; // if
+        let cond = self.expr()?;
+        let then = self.block()?;
+        let else_ = if self.eat_keyword("else") {
+            if self.is_keyword("if") {
+                Some(Box::new(self.nested(Self::if_expr)?))
+            } else {
+                Some(Box::new(Expr::Block(self.block()?)))
+            }
+        } else {
+            None
+        };
+        let end = else_.as_ref().map_or(then.span, |e| e.span());
+        Ok(Expr::If { cond: Box::new(cond), then, else_, span: start.to(end) })
+    }
+
+    fn labelled_loop(&mut self, label: Option<Ident>, start: Span) -> Result<Expr, Diagnostic> {
+        if self.eat_keyword("loop") {
+            let body = self.block()?;
+            let span = start.to(body.span);
+            Ok(Expr::Loop { label, body, span })
+        } else if self.eat_keyword("while") {
+            let cond = self.expr()?;
+            let body = self.block()?;
+            let span = start.to(body.span);
+            Ok(Expr::While { label, cond: Box::new(cond), body, span })
+        } else if self.eat_keyword("for") {
+            let var = self.ident()?;
+            if !self.eat_keyword("in") {
+                return Err(self.unexpected("`in`"));
+            }
+            let iter = self.expr()?;
+            let body = self.block()?;
+            let span = start.to(body.span);
+            Ok(Expr::For { label, var, iter: Box::new(iter), body, span })
+        } else {
+            Err(self.unexpected("`loop`, `while` or `for` after a label"))
+        }
+    }
+}
@@ -0,0 +1,435 @@
+// The type checker runs before anything is evaluated, as rustc would, and
+// stops at the first error. Every expression gets a type; `!` (never) is the
+// type of `break`, `continue` and a `loop` nothing breaks out of, and it fits
+// wherever another type is expected, which is what lets
+//
+//     let guess: i32 = if ok { 5 } else { continue };
+//
+// type-check. A `loop`'s type is the type of its `break` values; `while` and
+// `for` are always `()`.
+
+use std::collections::HashMap;
+
+use super::parser::{BinOp, Block, Expr, Ident, Stmt, UnOp};
+use super::Type;
+use crate::borrowck::{Diagnostic, Span};
+
+/// The type both `a` and `b` can be, if any: equal types, or the other one
+/// when either is `!`.
+pub fn unify(a: Type, b: Type) -> Option<Type> {
+    match (a, b) {
+        (Type::Never, other) | (other, Type::Never) => Some(other),
+        (a, b) if a == b => Some(a),
+        _ => None,
+    }
+}
+
+fn mismatch(expected: Type, found: Type, span: Span) -> Diagnostic {
+    Diagnostic::error(Some("E0308"), "mismatched types", span, &format!("expected `{expected}`, found `{found}`"))
+}
+
+struct Binding {
+    ty: Type,
+    mutable: bool,
+    decl: Span,
+}
+
+struct LoopFrame {
+    kind: &'static str,
+    label: Option<String>,
+    /// The type of the `break` values seen so far, and where the first was.
+    broken: Option<(Type, Span)>,
+}
+
+/// Checks a whole program and returns the type of its final expression.
+pub fn check(program: &Block) -> Result<Type, Diagnostic> {
+    let mut checker = Checker { scopes: Vec::new(), loops: Vec::new() };
+    checker.block(program)
+}
+
+struct Checker {
+    scopes: Vec<HashMap<String, Binding>>,
+    loops: Vec<LoopFrame>,
+}
+
+impl Checker {
+    fn lookup(&self, ident: &Ident) -> Result<&Binding, Diagnostic> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(&ident.name)).ok_or_else(|| {
+            Diagnostic::error(
+                Some("E0425"),
+                format!("cannot find value `{}` in this scope", ident.name),
+                ident.span,
+                "not found in this scope",
+            )
+        })
+    }
+
+    fn declare(&mut self, name: &Ident, ty: Type, mutable: bool) {
+        let binding = Binding { ty, mutable, decl: name.span };
+        self.scopes.last_mut().expect("declared outside any scope").insert(name.name.clone(), binding);
+    }
+
+    /// A block's type is its tail's, or `()` without one, or `!` when a
+    /// statement always breaks out first.
+    fn block(&mut self, block: &Block) -> Result<Type, Diagnostic> {
+        self.scopes.push(HashMap::new());
+        let result = self.block_body(block);
+        self.scopes.pop();
+        result
+    }
+
+    fn block_body(&mut self, block: &Block) -> Result<Type, Diagnostic> {
+        let mut diverges = false;
+        for stmt in &block.stmts {
+            diverges |= self.stmt(stmt)? == Type::Never;
+        }
+        match &block.tail {
+            Some(tail) => self.expr(tail),
+            None if diverges => Ok(Type::Never),
+            None => Ok(Type::Unit),
+        }
+    }
+
+    /// Checks a statement and returns the type of its expression, so the
+    /// enclosing block knows whether it diverges.
+    fn stmt(&mut self, stmt: &Stmt) -> Result<Type, Diagnostic> {
+        match stmt {
+            Stmt::Let { name, mutable, ty, init } => {
+                let found = self.expr(init)?;
+                let declared = match ty {
+                    None => found,
+                    Some((text, span)) => {
+                        let declared = Type::parse(text).ok_or_else(|| {
+                            Diagnostic::error(
+                                Some("E0412"),
+                                format!("cannot find type `{text}` in this scope"),
+                                *span,
+                                "not found in this scope",
+                            )
+                            .with_note("note: the types here are `i32`, `bool`, `&str` and `()`")
+                        })?;
+                        if unify(declared, found).is_none() {
+                            return Err(
+                                mismatch(declared, found, init.span()).with_label(*span, "expected due to this")
+                            );
+                        }
+                        declared
+                    }
+                };
+                self.declare(name, declared, *mutable);
+                Ok(found)
+            }
+            Stmt::Assign { target, op, value } => {
+                let binding = self.lookup(target)?;
+                let (ty, mutable, decl) = (binding.ty, binding.mutable, binding.decl);
+                let found = self.expr(value)?;
+                if !mutable {
+                    return Err(Diagnostic::error(
+                        Some("E0384"),
+                        format!("cannot assign twice to immutable variable `{}`", target.name),
+                        target.span,
+                        "cannot assign twice to immutable variable",
+                    )
+                    .with_label(decl, format!("first assignment to `{}`", target.name))
+                    .with_note(format!("help: consider making this binding mutable: `mut {}`", target.name)));
+                }
+                if let Some(op) = op {
+                    self.arithmetic(*op, ty, target.span, found, value.span())?;
+                } else if unify(ty, found).is_none() {
+                    return Err(
+                        mismatch(ty, found, value.span()).with_label(decl, "expected due to the type of this binding")
+                    );
+                }
+                Ok(Type::Unit)
+            }
+            Stmt::Expr { expr, semi } => {
+                let ty = self.expr(expr)?;
+                if !semi && unify(Type::Unit, ty).is_none() {
+                    return Err(mismatch(Type::Unit, ty, value_span(expr)).with_note(
+                        "help: a block-like expression used as a statement must be `()`; add a `;` after it",
+                    ));
+                }
+                Ok(ty)
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Result<Type, Diagnostic> {
+        match expr {
+            Expr::Int(value, span) => {
+                if *value > i64::from(i32::MAX) {
+                    return Err(out_of_range(*value, *span));
+                }
+                Ok(Type::I32)
+            }
+            Expr::Unit(_) => Ok(Type::Unit),
+            Expr::Bool(..) => Ok(Type::Bool),
+            Expr::Str(..) => Ok(Type::Str),
+            Expr::Var(ident) => Ok(self.lookup(ident)?.ty),
+            Expr::Unary { op, expr: operand, span } => {
+                // `-2147483648` is in range even though `2147483648` isn't.
+                let ty = match (op, &**operand) {
+                    (UnOp::Neg, Expr::Int(value, _)) if *value == -i64::from(i32::MIN) => Type::I32,
+                    _ => self.expr(operand)?,
+                };
+                match (op, ty) {
+                    (_, Type::Never) | (UnOp::Neg, Type::I32) | (UnOp::Not, Type::I32 | Type::Bool) => Ok(ty),
+                    (UnOp::Neg, _) | (UnOp::Not, _) => {
+                        let symbol = if *op == UnOp::Neg { "-" } else { "!" };
+                        Err(Diagnostic::error(
+                            Some("E0600"),
+                            format!("cannot apply unary operator `{symbol}` to type `{ty}`"),
+                            *span,
+                            &format!("cannot apply unary operator `{symbol}`"),
+                        ))
+                    }
+                }
+            }
+            Expr::Binary { op, lhs, rhs, .. } => {
+                let left = self.expr(lhs)?;
+                let right = self.expr(rhs)?;
+                match op {
+                    BinOp::And | BinOp::Or => {
+                        for (ty, side) in [(left, lhs), (right, rhs)] {
+                            if unify(Type::Bool, ty).is_none() {
+                                return Err(mismatch(Type::Bool, ty, side.span()));
+                            }
+                        }
+                        Ok(Type::Bool)
+                    }
+                    BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge => {
+                        let Some(ty) = unify(left, right) else {
+                            return Err(mismatch(left, right, rhs.span()));
+                        };
+                        // Like std: ranges have `==` but no ordering, and `.rev()` loses even `==`.
+                        let ordering = matches!(op, BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge);
+                        if matches!(ty, Type::Range { rev: true, .. }) || (ordering && matches!(ty, Type::Range { .. }))
+                        {
+                            return Err(cannot_apply(*op, ty, expr.span()));
+                        }
+                        Ok(Type::Bool)
+                    }
+                    _ => self.arithmetic(*op, left, lhs.span(), right, rhs.span()),
+                }
+            }
+            Expr::Block(block) => self.block(block),
+            Expr::If { cond, then, else_, span } => {
+                let cond_ty = self.expr(cond)?;
+                if unify(Type::Bool, cond_ty).is_none() {
+                    return Err(mismatch(Type::Bool, cond_ty, cond.span()).with_note(
+                        "note: Rust doesn't convert numbers to `bool`; compare instead, e.g. `number != 0`",
+                    ));
+                }
+                let then_ty = self.block(then)?;
+                let Some(else_) = else_ else {
+                    if unify(Type::Unit, then_ty).is_none() {
+                        return Err(Diagnostic::error(
+                            Some("E0317"),
+                            "`if` may be missing an `else` clause",
+                            *span,
+                            &format!("expected `{then_ty}`, found `()`"),
+                        )
+                        .with_label(then.value_span(), "found here")
+                        .with_note("note: `if` expressions without `else` evaluate to `()`")
+                        .with_note("help: consider adding an `else` block that evaluates to the expected type"));
+                    }
+                    return Ok(Type::Unit);
+                };
+                let else_ty = self.expr(else_)?;
+                unify(then_ty, else_ty).ok_or_else(|| {
+                    Diagnostic::error(
+                        Some("E0308"),
+                        "`if` and `else` have incompatible types",
+                        value_span(else_),
+                        &format!("expected `{then_ty}`, found `{else_ty}`"),
+                    )
+                    .with_label(then.value_span(), "expected because of this")
+                })
+            }
+            Expr::Loop { label, body, .. } => {
+                self.loops.push(LoopFrame {
+                    kind: "loop",
+                    label: label.as_ref().map(|l| l.name.clone()),
+                    broken: None,
+                });
+                let result = self.loop_body(body);
+                let frame = self.loops.pop().expect("loop frame pushed above");
+                result?;
+                // Nothing breaks out: the loop never ends.
+                Ok(frame.broken.map_or(Type::Never, |(ty, _)| ty))
+            }
+            Expr::While { label, cond, body, .. } => {
+                let cond_ty = self.expr(cond)?;
+                if unify(Type::Bool, cond_ty).is_none() {
+                    return Err(mismatch(Type::Bool, cond_ty, cond.span()));
+                }
+                self.loops.push(LoopFrame {
+                    kind: "while",
+                    label: label.as_ref().map(|l| l.name.clone()),
+                    broken: None,
+                });
+                let result = self.loop_body(body);
+                self.loops.pop();
+                result.map(|()| Type::Unit)
+            }
+            Expr::For { label, var, iter, body, .. } => {
+                let iter_ty = self.expr(iter)?;
+                if !matches!(iter_ty, Type::Range { .. }) {
+                    return Err(Diagnostic::error(
+                        Some("E0277"),
+                        format!("`{iter_ty}` is not an iterator"),
+                        iter.span(),
+                        &format!("`{iter_ty}` is not an iterator"),
+                    )
+                    .with_note("help: loop over a range instead, e.g. `0..n`"));
+                }
+                self.loops.push(LoopFrame { kind: "for", label: label.as_ref().map(|l| l.name.clone()), broken: None });
+                self.scopes.push(HashMap::new());
+                self.declare(var, Type::I32, false);
+                let result = self.loop_body(body);
+                self.scopes.pop();
+                self.loops.pop();
+                result.map(|()| Type::Unit)
+            }
+            Expr::Range { start, end, inclusive, rev, .. } => {
+                for bound in [start, end] {
+                    let ty = self.expr(bound)?;
+                    if unify(Type::I32, ty).is_none() {
+                        return Err(mismatch(Type::I32, ty, bound.span()));
+                    }
+                }
+                Ok(Type::Range { inclusive: *inclusive, rev: *rev })
+            }
+            Expr::Break { label, value, span } => {
+                let target = self.target("break", label.as_ref(), *span)?;
+                let ty = match value {
+                    Some(value) => self.expr(value)?,
+                    None => Type::Unit,
+                };
+                let frame = &mut self.loops[target];
+                if let Some(value) = value {
+                    if frame.kind != "loop" {
+                        return Err(Diagnostic::error(
+                            Some("E0571"),
+                            format!("`break` with value from a `{}` loop", frame.kind),
+                            *span,
+                            "can only break with a value inside `loop` or breakable block",
+                        )
+                        .with_label(value.span(), "remove this value")
+                        .with_note(format!(
+                            "help: use `break` on its own without a value inside this `{}` loop",
+                            frame.kind
+                        )));
+                    }
+                }
+                let at = value.as_ref().map_or(*span, |v| v.span());
+                frame.broken = match frame.broken {
+                    None => Some((ty, at)),
+                    Some((previous, first)) => match unify(previous, ty) {
+                        Some(ty) => Some((ty, first)),
+                        None => return Err(mismatch(previous, ty, at).with_label(first, "expected because of this")),
+                    },
+                };
+                Ok(Type::Never)
+            }
+            Expr::Continue { label, span } => {
+                self.target("continue", label.as_ref(), *span)?;
+                Ok(Type::Never)
+            }
+            Expr::Call { name, args, .. } => {
+                if name.name != "print" {
+                    return Err(Diagnostic::error(
+                        Some("E0425"),
+                        format!("cannot find function `{}` in this scope", name.name),
+                        name.span,
+                        "not found in this scope",
+                    )
+                    .with_note("note: the only function here is `print(...)`"));
+                }
+                let mut diverges = false;
+                for arg in args {
+                    diverges |= self.expr(arg)? == Type::Never;
+                }
+                Ok(if diverges { Type::Never } else { Type::Unit })
+            }
+        }
+    }
+
+    /// A loop body must be `()`: its value is thrown away every iteration.
+    fn loop_body(&mut self, body: &Block) -> Result<(), Diagnostic> {
+        let ty = self.block(body)?;
+        match unify(Type::Unit, ty) {
+            Some(_) => Ok(()),
+            None => Err(mismatch(Type::Unit, ty, body.value_span())
+                .with_note("help: a loop body's value is discarded; use `break value` to return it from a `loop`")),
+        }
+    }
+
+    /// The index in `self.loops` of the loop a `break` or `continue` leaves.
+    fn target(&self, keyword: &str, label: Option<&Ident>, span: Span) -> Result<usize, Diagnostic> {
+        match label {
+            None => self.loops.len().checked_sub(1).ok_or_else(|| {
+                Diagnostic::error(
+                    Some("E0268"),
+                    format!("`{keyword}` outside of a loop"),
+                    span,
+                    &format!("cannot `{keyword}` outside of a loop"),
+                )
+            }),
+            Some(label) => {
+                self.loops.iter().rposition(|frame| frame.label.as_deref() == Some(label.name.as_str())).ok_or_else(
+                    || {
+                        Diagnostic::error(
+                            Some("E0426"),
+                            format!("use of undeclared label `{}`", label.name),
+                            label.span,
+                            &format!("undeclared label `{}`", label.name),
+                        )
+                    },
+                )
+            }
+        }
+    }
+
+    /// `+ - * / %` take two `i32`s.
+    fn arithmetic(
+        &self,
+        op: BinOp,
+        left: Type,
+        left_span: Span,
+        right: Type,
+        right_span: Span,
+    ) -> Result<Type, Diagnostic> {
+        if unify(Type::I32, left).is_none() {
+            return Err(cannot_apply(op, left, left_span.to(right_span)));
+        }
+        if unify(Type::I32, right).is_none() {
+            return Err(mismatch(Type::I32, right, right_span));
+        }
+        Ok(Type::I32)
+    }
+}
+
+fn cannot_apply(op: BinOp, ty: Type, span: Span) -> Diagnostic {
+    Diagnostic::error(
+        Some("E0369"),
+        format!("binary operation `{}` cannot be applied to type `{ty}`", op.symbol()),
+        span,
+        "",
+    )
+}
+
+fn out_of_range(value: i64, span: Span) -> Diagnostic {
+    Diagnostic::error(None, "literal out of range for `i32`", span, "").with_note(format!(
+        "note: the literal `{value}` does not fit into the type `i32` whose range is `-2147483648..=2147483647`"
+    ))
+}
+
+/// Where an expression's value comes from, for pointing at it in errors:
+/// the tail of a block rather than the whole block.
+fn value_span(expr: &Expr) -> Span {
+    match expr {
+        Expr::Block(block) => block.value_span(),
+        other => other.span(),
+    }
+}
@@ -0,0 +1,26 @@
+//! Shared code for the lesson binaries in `src/bin`.
+//!
+//! Each lesson keeps its prose as comments next to named, runnable examples;
+//! anything more than one lesson needs lives here so it can be imported and
+//! tested.
+
+pub mod alloc_counter;
+pub mod bindings;
+pub mod borrowck;
+pub mod borrowing;
+pub mod chars;
+pub mod compound;
+pub mod countdown;
+pub mod diagram;
+pub mod divisibility;
+pub mod examples;
+pub mod floats;
+pub mod functions;
+pub mod integers;
+pub mod interpreter;
+pub mod lifetimes;
+pub mod loop_bench;
+pub mod loop_trace;
+pub mod ownership;
+pub mod slices;
+pub mod traced;
@@ -0,0 +1,174 @@
+// Dangling references and lifetimes
+// "References must always be valid": a reference can't outlive the value it
+// points to. The compiler checks this by giving every reference a lifetime;
+// usually it works them out itself, and when it can't tell how a returned or
+// stored reference relates to its sources, it asks for annotations like `'a`.
+//
+// The functions below are the versions that compile. `CASES` lists them next
+// to the versions that don't, with the rule each one breaks; `explain` prints
+// that table. Every rejected case is also a file under
+// `compile_fail/references_borrowing/`, checked against rustc.
+
+use std::fmt::Write as _;
+
+/// The fix for `dangle`: return the String itself. Ownership moves out to
+/// the caller, so nothing is deallocated.
+#[allow(clippy::let_and_return)] // mirrors the book's listing
+pub fn no_dangle() -> String {
+    let s = String::from("hello");
+
+    s
+}
+
+/// The longer of two string slices. The result lives as long as the shorter
+/// of the two inputs' lifetimes, because it may be either of them.
+pub fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+/// Returns `x` whatever `y` is, so only `x` needs to share the result's
+/// lifetime; `y` can be dropped while the result is still in use.
+pub fn first_of<'a>(x: &'a str, _y: &str) -> &'a str {
+    x
+}
+
+/// A struct holding a reference can't outlive the text it points into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImportantExcerpt<'a> {
+    pub part: &'a str,
+}
+
+impl<'a> ImportantExcerpt<'a> {
+    /// The first sentence of `text` (up to the first `.`), or all of it.
+    pub fn first_sentence(text: &'a str) -> Self {
+        ImportantExcerpt { part: text.split('.').next().unwrap_or(text) }
+    }
+
+    pub fn level(&self) -> i32 {
+        3
+    }
+
+    /// Elision gives the result the lifetime of `&self`, not of
+    /// `announcement`.
+    pub fn announce_and_return_part(&self, announcement: &str) -> &str {
+        println!("Attention please: {announcement}");
+        self.part
+    }
+}
+
+/// Holds one word of each of two texts, which may have different lifetimes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pair<'a, 'b> {
+    pub left: &'a str,
+    pub right: &'b str,
+}
+
+/// A lesson snippet and the reference rule it keeps or breaks.
+#[derive(Clone, Copy, Debug)]
+pub struct Case {
+    /// Short form of the snippet, e.g. `fn dangle() -> &String`.
+    pub snippet: &'static str,
+    /// rustc's error code, or `None` if the snippet compiles.
+    pub code: Option<&'static str>,
+    /// The rule in play.
+    pub rule: &'static str,
+    /// What happens, in a sentence.
+    pub why: &'static str,
+    /// File name under `compile_fail/references_borrowing/` for rejected snippets.
+    pub compile_fail: Option<&'static str>,
+}
+
+const VALID: &str = "References must always be valid";
+const TIED: &str = "A returned reference must say which input it borrows from";
+const STORED: &str = "A struct holding a reference needs a lifetime parameter";
+
+pub const CASES: &[Case] = &[
+    Case {
+        snippet: "fn dangle() -> &String { let s = String::from(\"hello\"); &s }",
+        code: Some("E0106"),
+        rule: VALID,
+        why: "s is dropped when dangle ends, so there is nothing for the reference to borrow from; \
+              with no parameters, the compiler can't even name a lifetime for it",
+        compile_fail: Some("dangle.rs"),
+    },
+    Case {
+        snippet: "fn dangle<'a>() -> &'a String { let s = String::from(\"hello\"); &s }",
+        code: Some("E0515"),
+        rule: VALID,
+        why: "naming a lifetime doesn't help: &s still points at a local that is dropped on return",
+        compile_fail: Some("dangle_with_lifetime.rs"),
+    },
+    Case {
+        snippet: "fn no_dangle() -> String { let s = String::from(\"hello\"); s }",
+        code: None,
+        rule: VALID,
+        why: "the String itself is returned, so ownership moves out and nothing is deallocated",
+        compile_fail: None,
+    },
+    Case {
+        snippet: "fn longest(x: &str, y: &str) -> &str",
+        code: Some("E0106"),
+        rule: TIED,
+        why: "the result could borrow from x or from y, and elision can't pick one",
+        compile_fail: Some("longest_without_lifetime.rs"),
+    },
+    Case {
+        snippet: "fn longest<'a>(x: &'a str, y: &'a str) -> &'a str",
+        code: None,
+        rule: TIED,
+        why: "the result is valid for as long as both inputs are",
+        compile_fail: None,
+    },
+    Case {
+        snippet: "result = longest(string1.as_str(), string2.as_str()); // string2 dropped, result used",
+        code: Some("E0597"),
+        rule: VALID,
+        why: "'a is the shorter of the two lifetimes, and string2's ends before result's last use",
+        compile_fail: Some("longest_outlives_input.rs"),
+    },
+    Case {
+        snippet: "struct ImportantExcerpt { part: &str }",
+        code: Some("E0106"),
+        rule: STORED,
+        why: "the struct's users need to know how long part stays valid",
+        compile_fail: Some("struct_without_lifetime.rs"),
+    },
+    Case {
+        snippet: "struct ImportantExcerpt<'a> { part: &'a str }",
+        code: None,
+        rule: STORED,
+        why: "an ImportantExcerpt can't outlive the text its part points into",
+        compile_fail: None,
+    },
+    Case {
+        snippet: "i = ImportantExcerpt { part: &novel[..] }; // novel dropped, i used",
+        code: Some("E0597"),
+        rule: VALID,
+        why: "the excerpt is used after the String it borrows from goes out of scope",
+        compile_fail: Some("excerpt_outlives_text.rs"),
+    },
+];
+
+/// Renders `CASES` as a table: snippet, verdict, rule and explanation.
+pub fn explain() -> String {
+    let mut out = String::new();
+    for (i, case) in CASES.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        writeln!(out, "{}", case.snippet).unwrap();
+        match (case.code, case.compile_fail) {
+            (Some(code), Some(file)) => writeln!(out, "  error[{code}] (compile_fail/references_borrowing/{file})"),
+            (Some(code), None) => writeln!(out, "  error[{code}]"),
+            (None, _) => writeln!(out, "  compiles"),
+        }
+        .unwrap();
+        writeln!(out, "  rule: {}", case.rule).unwrap();
+        writeln!(out, "  why:  {}", case.why).unwrap();
+    }
+    out
+}
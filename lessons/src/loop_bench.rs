@@ -0,0 +1,254 @@
+// Loop benchmarks
+// The control-flow lesson says the `while index < 5` loop is slow "because the
+// compiler adds runtime code to perform the conditional check" on every
+// `a[index]`. This module measures it instead: the same sum written five ways,
+// timed over arrays of several sizes.
+//
+//     cargo run --release --bin control_flow -- bench
+//
+// Timing needs nothing but `Instant` and `black_box`, so it runs offline.
+// Each variant is run enough times to fill `Config::sample` per sample, and
+// the fastest of `Config::samples` samples is kept, as the least disturbed by
+// whatever else the machine was doing. Debug builds keep every bounds check
+// and no inlining, so only a `--release` run says much about the claim; in
+// release, LLVM can often prove `index < a.len()` and drop the check itself.
+// A loop that stops at a bound of its own, like the lesson's `index < 5`, is
+// timed too: there the compiler can't lean on `a.len()`, though in release it
+// still gets away with one check of the last index before the loop.
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Sums `a` with a manual index, like the lesson's `while index < 5`. Every
+/// `a[index]` is bounds checked unless the optimiser proves it can't fail.
+pub fn sum_while_index(a: &[u64]) -> u64 {
+    let mut sum: u64 = 0;
+    let mut index = 0;
+
+    while index < a.len() {
+        sum = sum.wrapping_add(a[index]);
+
+        index += 1;
+    }
+    sum
+}
+
+/// Sums the first `bound` elements of `a` with a manual index, the way the
+/// lesson writes `while index < 5`: the bound isn't `a.len()`, so each
+/// `a[index]` is checked unless the optimiser can hoist the check.
+///
+/// Panics if `bound > a.len()`, like the lesson's loop would with a shorter
+/// array.
+pub fn sum_while_bound(a: &[u64], bound: usize) -> u64 {
+    let mut sum: u64 = 0;
+    let mut index = 0;
+
+    while index < bound {
+        sum = sum.wrapping_add(a[index]);
+
+        index += 1;
+    }
+    sum
+}
+
+/// `sum_while_bound` over all of `a`, with the length hidden from the
+/// optimiser so it can't tell the bound and the length are the same.
+fn sum_while_hidden_len(a: &[u64]) -> u64 {
+    sum_while_bound(a, black_box(a.len()))
+}
+
+/// Sums `a` with `for element in a`: no index, so nothing to check.
+pub fn sum_for(a: &[u64]) -> u64 {
+    let mut sum: u64 = 0;
+    for element in a {
+        sum = sum.wrapping_add(*element);
+    }
+    sum
+}
+
+/// Sums `a` with an iterator adaptor.
+pub fn sum_iter(a: &[u64]) -> u64 {
+    a.iter().fold(0, |sum, &element| sum.wrapping_add(element))
+}
+
+/// Sums `a` with a manual index but no bounds checks at all.
+pub fn sum_unchecked(a: &[u64]) -> u64 {
+    let mut sum: u64 = 0;
+    let mut index = 0;
+
+    while index < a.len() {
+        // SAFETY: the loop condition keeps `index` below `a.len()`.
+        sum = sum.wrapping_add(unsafe { *a.get_unchecked(index) });
+
+        index += 1;
+    }
+    sum
+}
+
+/// One way of writing the loop.
+#[derive(Clone, Copy, Debug)]
+pub struct Variant {
+    pub name: &'static str,
+    pub run: fn(&[u64]) -> u64,
+}
+
+pub const VARIANTS: &[Variant] = &[
+    Variant { name: "while index", run: sum_while_index },
+    Variant { name: "while < bound", run: sum_while_hidden_len },
+    Variant { name: "for element", run: sum_for },
+    Variant { name: "iter fold", run: sum_iter },
+    Variant { name: "get_unchecked", run: sum_unchecked },
+];
+
+/// How long to measure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    pub sizes: Vec<usize>,
+    /// Time to aim for per sample.
+    pub sample: Duration,
+    pub samples: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { sizes: vec![5, 64, 1_024, 65_536, 1_048_576], sample: Duration::from_millis(20), samples: 5 }
+    }
+}
+
+/// The best sample for one variant at one size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    pub variant: &'static str,
+    pub size: usize,
+    /// Calls per sample.
+    pub iterations: u64,
+    pub per_call: Duration,
+}
+
+impl Measurement {
+    pub fn ns_per_element(&self) -> f64 {
+        self.per_call.as_secs_f64() * 1e9 / self.size.max(1) as f64
+    }
+}
+
+/// Times `calls` calls of `run` on `a`.
+fn time(run: fn(&[u64]) -> u64, a: &[u64], calls: u64) -> Duration {
+    let start = Instant::now();
+    for _ in 0..calls {
+        black_box(run(black_box(a)));
+    }
+    start.elapsed()
+}
+
+/// Doubles the number of calls until one sample takes at least `sample`.
+fn calibrate(run: fn(&[u64]) -> u64, a: &[u64], sample: Duration) -> u64 {
+    let mut calls = 1;
+    while time(run, a, calls) < sample && calls < 1 << 40 {
+        calls *= 2;
+    }
+    calls
+}
+
+/// The array every variant sums: `0, 1, 2, ...`.
+pub fn input(size: usize) -> Vec<u64> {
+    (0..size as u64).collect()
+}
+
+/// Runs every variant at every size.
+///
+/// Panics if the variants disagree on a sum: timing a wrong answer is
+/// pointless.
+pub fn run(config: &Config) -> Report {
+    let mut measurements = Vec::new();
+    for &size in &config.sizes {
+        let a = input(size);
+        let expected = sum_for(&a);
+        for variant in VARIANTS {
+            assert_eq!((variant.run)(&a), expected, "{} summed {size} elements wrong", variant.name);
+            let iterations = calibrate(variant.run, &a, config.sample);
+            let best = (0..config.samples.max(1)).map(|_| time(variant.run, &a, iterations)).min().unwrap();
+            let per_call = Duration::from_secs_f64(best.as_secs_f64() / iterations as f64);
+            measurements.push(Measurement { variant: variant.name, size, iterations, per_call });
+        }
+    }
+    Report { measurements, optimized: !cfg!(debug_assertions) }
+}
+
+/// Everything `run` measured.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub measurements: Vec<Measurement>,
+    /// Whether this was a release build.
+    pub optimized: bool,
+}
+
+impl Report {
+    pub fn get(&self, variant: &str, size: usize) -> Option<&Measurement> {
+        self.measurements.iter().find(|m| m.variant == variant && m.size == size)
+    }
+
+    fn sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = Vec::new();
+        for m in &self.measurements {
+            if !sizes.contains(&m.size) {
+                sizes.push(m.size);
+            }
+        }
+        sizes
+    }
+}
+
+/// The indexed `while` loops compared with `for` in the table, and their
+/// column headings.
+const RATIOS: &[(&str, &str)] = &[("while index", "while / for"), ("while < bound", "bound / for")];
+
+/// ns per element for each variant, one row per size, and how the indexed
+/// `while` loops compare with `for`.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let profile = if self.optimized { "release" } else { "debug: run with --release for meaningful numbers" };
+        writeln!(f, "ns per element, best of each sample ({profile})")?;
+
+        let mut header = vec!["size".to_string()];
+        header.extend(VARIANTS.iter().map(|v| v.name.to_string()));
+        header.extend(RATIOS.iter().map(|(_, label)| label.to_string()));
+        let rows: Vec<Vec<String>> = self
+            .sizes()
+            .into_iter()
+            .map(|size| {
+                let mut row = vec![size.to_string()];
+                for variant in VARIANTS {
+                    row.push(
+                        self.get(variant.name, size).map_or("-".to_string(), |m| format!("{:.3}", m.ns_per_element())),
+                    );
+                }
+                for (variant, _) in RATIOS {
+                    let ratio = match (self.get(variant, size), self.get("for element", size)) {
+                        (Some(w), Some(f)) if !f.per_call.is_zero() => {
+                            format!("{:.2}x", w.per_call.as_secs_f64() / f.per_call.as_secs_f64())
+                        }
+                        _ => "-".to_string(),
+                    };
+                    row.push(ratio);
+                }
+                row
+            })
+            .collect();
+
+        let mut widths: Vec<usize> = header.iter().map(String::len).collect();
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        let line = |cells: &[String]| -> String {
+            cells.iter().zip(&widths).map(|(cell, &w)| format!("{cell:>w$}")).collect::<Vec<_>>().join("  ")
+        };
+        writeln!(f, "{}", line(&header))?;
+        for row in &rows {
+            writeln!(f, "{}", line(row))?;
+        }
+        Ok(())
+    }
+}
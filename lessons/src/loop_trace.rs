@@ -0,0 +1,256 @@
+// Loop traces
+// `break` and `continue` apply to the innermost loop unless they name a
+// label, and with `'counting_up:` nested around another loop it's easy to
+// lose track of which one a `break` leaves. A `LoopTrace` is told about each
+// loop, iteration, `continue` and `break` as the real loops run, keeps its own
+// stack of the loops it's inside, and renders what happened as an indented
+// timeline:
+//
+//     let mut t = LoopTrace::new();
+//     t.enter(Kind::Loop, Some("'counting_up"));
+//     'counting_up: loop {
+//         t.iteration();
+//         ...
+//         t.break_loop(Some("'counting_up"));
+//         break 'counting_up;
+//     }
+//     print!("{t}");
+//
+// Breaking out of an outer loop also ends every loop inside it, so one
+// `break 'counting_up` shows up as a break followed by two loop exits.
+
+use std::fmt;
+
+/// Which loop keyword a loop uses; only `loop` can `break` with a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Loop,
+    While,
+    For,
+}
+
+impl Kind {
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Kind::Loop => "loop",
+            Kind::While => "while",
+            Kind::For => "for",
+        }
+    }
+}
+
+/// Something that happened in a traced loop. `depth` is 1 for the outermost
+/// traced loop; `label` on a `break` or `continue` is the label written in the
+/// source, if any, and `target_depth` the loop it applies to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    Enter {
+        depth: usize,
+        kind: Kind,
+        label: Option<String>,
+    },
+    Iteration {
+        depth: usize,
+        number: usize,
+    },
+    /// Whatever the loop body printed, shown at the body's indentation.
+    Note {
+        depth: usize,
+        text: String,
+    },
+    Continue {
+        depth: usize,
+        label: Option<String>,
+        target_depth: usize,
+    },
+    Break {
+        depth: usize,
+        label: Option<String>,
+        target_depth: usize,
+        value: Option<String>,
+    },
+    /// A loop ended, with how many iterations it started and the value it
+    /// produced, if it was broken out of with one.
+    Exit {
+        depth: usize,
+        kind: Kind,
+        label: Option<String>,
+        iterations: usize,
+        value: Option<String>,
+    },
+}
+
+struct Frame {
+    kind: Kind,
+    label: Option<String>,
+    iterations: usize,
+}
+
+/// Records what nested loops do, for display as a timeline.
+#[derive(Default)]
+pub struct LoopTrace {
+    stack: Vec<Frame>,
+    events: Vec<Event>,
+}
+
+impl LoopTrace {
+    pub fn new() -> Self {
+        LoopTrace::default()
+    }
+
+    /// Records the start of a loop, before its first iteration.
+    pub fn enter(&mut self, kind: Kind, label: Option<&str>) {
+        self.stack.push(Frame { kind, label: label.map(str::to_string), iterations: 0 });
+        self.events.push(Event::Enter { depth: self.depth(), kind, label: label.map(str::to_string) });
+    }
+
+    /// Records the start of an iteration of the innermost loop.
+    ///
+    /// Panics outside any traced loop.
+    pub fn iteration(&mut self) {
+        let depth = self.depth();
+        let frame = self.stack.last_mut().expect("iteration outside any traced loop");
+        frame.iterations += 1;
+        self.events.push(Event::Iteration { depth, number: frame.iterations });
+    }
+
+    /// Records a line of output from the loop body.
+    pub fn note(&mut self, text: impl Into<String>) {
+        self.events.push(Event::Note { depth: self.depth(), text: text.into() });
+    }
+
+    /// Records `continue` or `continue 'label`: every loop inside the target
+    /// ends, and the target goes on to its next iteration.
+    pub fn continue_loop(&mut self, label: Option<&str>) {
+        let target_depth = self.target(label);
+        self.events.push(Event::Continue { depth: self.depth(), label: label.map(str::to_string), target_depth });
+        while self.depth() > target_depth {
+            self.pop(None);
+        }
+    }
+
+    /// Records `break` or `break 'label`: the target and every loop inside it
+    /// end.
+    pub fn break_loop(&mut self, label: Option<&str>) {
+        self.break_with(label, None);
+    }
+
+    /// Records `break value` or `break 'label value`.
+    ///
+    /// Panics if the target isn't a `loop`: rustc rejects `break` with a value
+    /// from `while` and `for` (E0571).
+    pub fn break_value(&mut self, label: Option<&str>, value: &impl fmt::Debug) {
+        let target_depth = self.target(label);
+        let kind = self.stack[target_depth - 1].kind;
+        assert!(kind == Kind::Loop, "error[E0571]: `break` with value from a `{}` loop", kind.keyword());
+        self.break_with(label, Some(format!("{value:?}")));
+    }
+
+    fn break_with(&mut self, label: Option<&str>, value: Option<String>) {
+        let target_depth = self.target(label);
+        self.events.push(Event::Break {
+            depth: self.depth(),
+            label: label.map(str::to_string),
+            target_depth,
+            value: value.clone(),
+        });
+        while self.depth() > target_depth {
+            self.pop(None);
+        }
+        self.pop(value);
+    }
+
+    /// Records a loop ending on its own: a `while` condition turning false or
+    /// a `for` running out of items.
+    pub fn exit(&mut self) {
+        self.pop(None);
+    }
+
+    fn pop(&mut self, value: Option<String>) {
+        let depth = self.depth();
+        let frame = self.stack.pop().expect("exit outside any traced loop");
+        self.events.push(Event::Exit {
+            depth,
+            kind: frame.kind,
+            label: frame.label,
+            iterations: frame.iterations,
+            value,
+        });
+    }
+
+    /// The depth of the loop a `break` or `continue` with `label` applies to.
+    ///
+    /// Panics if there's no such loop, as rustc would refuse the label
+    /// (E0426) or the bare keyword outside a loop (E0268).
+    fn target(&self, label: Option<&str>) -> usize {
+        match label {
+            None => {
+                assert!(!self.stack.is_empty(), "error[E0268]: `break` or `continue` outside of a loop");
+                self.depth()
+            }
+            Some(label) => {
+                let index = self.stack.iter().rposition(|frame| frame.label.as_deref() == Some(label));
+                index.unwrap_or_else(|| panic!("error[E0426]: use of undeclared label `{label}`")) + 1
+            }
+        }
+    }
+
+    /// How many traced loops are running.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+}
+
+fn named(kind: Kind, label: &Option<String>) -> String {
+    match label {
+        Some(label) => format!("{label}: {}", kind.keyword()),
+        None => kind.keyword().to_string(),
+    }
+}
+
+fn plural(n: usize, one: &str) -> String {
+    if n == 1 {
+        format!("1 {one}")
+    } else {
+        format!("{n} {one}s")
+    }
+}
+
+/// One line per event: the depth, then the event indented so that a loop's
+/// iterations sit inside it and each iteration's body inside that.
+impl fmt::Display for LoopTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for event in &self.events {
+            // In units of two spaces: a loop at depth d opens at 2(d - 1), its
+            // iterations at 2d - 1 and their bodies at 2d.
+            let (depth, indent, text) = match event {
+                Event::Enter { depth, kind, label } => (depth, 2 * depth - 2, format!("{} {{", named(*kind, label))),
+                Event::Iteration { depth, number } => (depth, 2 * depth - 1, format!("iteration {number}")),
+                Event::Note { depth, text } => (depth, 2 * depth, text.clone()),
+                Event::Continue { depth, label, target_depth } => {
+                    let keyword = label.as_ref().map_or("continue".to_string(), |l| format!("continue {l}"));
+                    (depth, 2 * depth, format!("{keyword}  -> next iteration at depth {target_depth}"))
+                }
+                Event::Break { depth, label, target_depth, value } => {
+                    let mut keyword = label.as_ref().map_or("break".to_string(), |l| format!("break {l}"));
+                    if let Some(value) = value {
+                        keyword = format!("{keyword} {value}");
+                    }
+                    let leaves = plural(depth - target_depth + 1, "loop");
+                    (depth, 2 * depth, format!("{keyword}  -> leaves {leaves}, back to depth {}", target_depth - 1))
+                }
+                Event::Exit { depth, kind, label, iterations, value } => {
+                    let result = value.as_ref().map_or(String::new(), |v| format!(" = {v}"));
+                    let iterations = plural(*iterations, "iteration");
+                    (depth, 2 * depth - 2, format!("}} end of {} after {iterations}{result}", named(*kind, label)))
+                }
+            };
+            writeln!(f, "{depth}  {}{text}", "  ".repeat(indent))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+// Ownership and Functions
+// Before references: a function that needs a String has to take ownership of
+// it, so it must hand the String back if the caller wants to keep using it.
+
+/// Listing 4-5: takes ownership of `s` and returns it along with its length.
+/// Compare `borrowing::calculate_length`, which only borrows.
+pub fn calculate_length(s: String) -> (String, usize) {
+    let length = s.len(); // len() returns the length of a String
+
+    (s, length)
+}
@@ -0,0 +1,61 @@
+// The Slice Type
+// A string slice is a reference to part of a String: a pointer into the
+// String's buffer plus a length, both in bytes. Because it is a borrow, the
+// String can't be changed or dropped while the slice is in use, which is what
+// makes returning a slice safer than returning an index.
+//
+// Words here are separated by whitespace (`char::is_whitespace`). Every cut is
+// made at a position found by walking the string's chars, so slicing never
+// lands inside a multi-byte character like 'ℤ' (3 bytes) or '😻' (4 bytes).
+
+/// Byte index of the end of the first word: Listing 4-7's `first_word`,
+/// which returns a number that knows nothing about the String it came from.
+/// Clearing the String leaves the index as it was, pointing at nothing.
+pub fn first_word_end(s: &str) -> usize {
+    s.char_indices().find(|(_, c)| c.is_whitespace()).map_or(s.len(), |(i, _)| i)
+}
+
+/// The first word of `s`: everything before the first whitespace character,
+/// or all of `s` if it has none. Takes `&str` so it works on `&String`
+/// (through deref coercion), string literals and other slices alike.
+pub fn first_word(s: &str) -> &str {
+    &s[..first_word_end(s)]
+}
+
+/// The `n`th word of `s` (counting from 0), skipping runs of whitespace.
+pub fn nth_word(s: &str, n: usize) -> Option<&str> {
+    words(s).nth(n)
+}
+
+/// An iterator over the words of `s`, each one a slice of `s`.
+pub fn words(s: &str) -> Words<'_> {
+    Words { rest: s }
+}
+
+/// Iterator returned by `words`. It holds what is left of the original
+/// string, so every word it yields borrows from that string, not from the
+/// iterator.
+#[derive(Clone, Debug)]
+pub struct Words<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let start = self.rest.char_indices().find(|(_, c)| !c.is_whitespace()).map(|(i, _)| i)?;
+        let rest = &self.rest[start..];
+        let end = first_word_end(rest);
+        self.rest = &rest[end..];
+        Some(&rest[..end])
+    }
+}
+
+/// Byte range of `word` inside `s`, when `word` is a slice of `s` (as the
+/// results of `first_word`, `nth_word` and `words` are).
+pub fn byte_range(s: &str, word: &str) -> Option<std::ops::Range<usize>> {
+    let start = (word.as_ptr() as usize).checked_sub(s.as_ptr() as usize)?;
+    let end = start + word.len();
+    (end <= s.len()).then_some(start..end)
+}
@@ -0,0 +1,178 @@
+// TracedString
+// A String that writes down what happens to it. The ownership lesson describes
+// allocation, moves, clones and drops in comments; with a TracedString the
+// examples can show each of those events as it happens.
+//
+// Moves are plain memcpys the compiler never tells us about, so a move is only
+// logged when the code says so with `.moved("new_owner")`. Allocations, clones
+// and drops are logged automatically.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::ops::Deref;
+use std::panic::Location;
+
+/// Something that happened to a [`TracedString`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// `TracedString::new` asked the allocator for a heap buffer.
+    Alloc { name: String, value: String, capacity: usize, at: &'static Location<'static> },
+    /// Ownership passed from `from` to `to`; the heap buffer stayed where it was.
+    Move { from: String, to: String, at: &'static Location<'static> },
+    /// `clone` copied the heap data into a new buffer owned by `to`.
+    Clone { from: String, to: String, capacity: usize, at: &'static Location<'static> },
+    /// The owner went out of scope and its heap buffer was freed.
+    Drop { name: String, value: String },
+}
+
+impl Event {
+    /// Where in the source the event happened. Drops are inserted by the
+    /// compiler at the end of a scope, so they have no call site.
+    pub fn location(&self) -> Option<&'static Location<'static>> {
+        match self {
+            Event::Alloc { at, .. } | Event::Move { at, .. } | Event::Clone { at, .. } => Some(at),
+            Event::Drop { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Alloc { name, value, capacity, .. } => {
+                write!(f, "alloc  {name} = {value:?} ({capacity} bytes on the heap)")?
+            }
+            Event::Move { from, to, .. } => write!(f, "move   {from} -> {to} (no heap copy)")?,
+            Event::Clone { from, to, capacity, .. } => {
+                write!(f, "clone  {from} -> {to} (deep copy, {capacity} new bytes on the heap)")?
+            }
+            Event::Drop { name, value } => write!(f, "drop   {name} = {value:?} (heap freed)")?,
+        }
+        match self.location() {
+            Some(at) => write!(f, " at {}", short_location(at)),
+            None => Ok(()),
+        }
+    }
+}
+
+fn short_location(at: &Location<'_>) -> String {
+    let file = at.file().rsplit(['/', '\\']).next().unwrap_or(at.file());
+    format!("{file}:{}", at.line())
+}
+
+#[derive(Default)]
+struct Log {
+    events: Vec<Event>,
+    echo: bool,
+}
+
+thread_local! {
+    static LOG: RefCell<Log> = RefCell::new(Log::default());
+}
+
+fn record(event: Event) {
+    // `try_with`: a TracedString can still be dropped while the thread-local
+    // itself is being torn down.
+    let _ = LOG.try_with(|log| {
+        let mut log = log.borrow_mut();
+        if log.echo {
+            println!("  [{event}]");
+        }
+        log.events.push(event);
+    });
+}
+
+/// Print every event to stdout as it is recorded (on this thread).
+pub fn set_echo(echo: bool) {
+    LOG.with(|log| log.borrow_mut().echo = echo);
+}
+
+/// Removes and returns the events recorded so far on this thread.
+pub fn take_events() -> Vec<Event> {
+    LOG.with(|log| std::mem::take(&mut log.borrow_mut().events))
+}
+
+/// A `String` with a binding name whose lifecycle is recorded to the event log.
+pub struct TracedString {
+    name: String,
+    value: String,
+}
+
+impl TracedString {
+    /// Like `String::from(value)`, recorded as an allocation owned by `name`.
+    #[track_caller]
+    pub fn new(name: &str, value: &str) -> Self {
+        let traced = TracedString { name: name.to_string(), value: String::from(value) };
+        record(Event::Alloc {
+            name: traced.name.clone(),
+            value: traced.value.clone(),
+            capacity: traced.value.capacity(),
+            at: Location::caller(),
+        });
+        traced
+    }
+
+    /// Records that ownership moves to the binding `to` and returns the value
+    /// so it can be passed on, e.g. `takes_ownership(s.moved("some_string"))`.
+    #[track_caller]
+    pub fn moved(mut self, to: &str) -> Self {
+        let from = std::mem::replace(&mut self.name, to.to_string());
+        record(Event::Move { from, to: to.to_string(), at: Location::caller() });
+        self
+    }
+
+    /// Deep-copies the heap data into a new value owned by `name`.
+    #[track_caller]
+    pub fn clone_as(&self, name: &str) -> Self {
+        let copy = TracedString { name: name.to_string(), value: self.value.clone() };
+        record(Event::Clone {
+            from: self.name.clone(),
+            to: copy.name.clone(),
+            capacity: copy.value.capacity(),
+            at: Location::caller(),
+        });
+        copy
+    }
+
+    /// The binding that currently owns this value.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Clone for TracedString {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        self.clone_as(&format!("{}.clone()", self.name))
+    }
+}
+
+impl Drop for TracedString {
+    fn drop(&mut self) {
+        record(Event::Drop { name: self.name.clone(), value: self.value.clone() });
+    }
+}
+
+impl Deref for TracedString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for TracedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl fmt::Debug for TracedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {:?}", self.name, self.value)
+    }
+}
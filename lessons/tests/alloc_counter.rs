@@ -0,0 +1,69 @@
+use lessons::alloc_counter::{measure, CountingAllocator, Stats};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn string_from_allocates_exactly_once() {
+    let (s, stats) = measure(|| String::from("hello"));
+    assert_eq!(stats, Stats { allocations: 1, bytes_allocated: 5, ..Stats::default() });
+    assert_eq!(s, "hello");
+}
+
+#[test]
+fn a_move_never_allocates() {
+    let s1 = String::from("hello");
+    let (s2, stats) = measure(|| s1);
+    assert_eq!(stats, Stats::default());
+    assert_eq!(s2, "hello");
+}
+
+#[test]
+fn clone_allocates_a_copy_of_the_heap_data() {
+    let s1 = String::from("hello");
+    let (s2, stats) = measure(|| s1.clone());
+    assert_eq!(stats, Stats { allocations: 1, bytes_allocated: 5, ..Stats::default() });
+    assert_ne!(s1.as_ptr(), s2.as_ptr());
+}
+
+#[test]
+fn push_str_past_capacity_reallocates_once() {
+    let mut s = String::from("hello");
+    let ((), stats) = measure(|| s.push_str(", world!"));
+    assert_eq!(stats.allocations, 0);
+    assert_eq!(stats.reallocations, 1);
+    assert_eq!(s, "hello, world!");
+}
+
+#[test]
+fn dropping_a_string_frees_its_buffer() {
+    let s = String::from("hello");
+    let ((), stats) = measure(|| drop(s));
+    assert_eq!(stats, Stats { deallocations: 1, ..Stats::default() });
+}
+
+#[test]
+fn nested_measurements_add_up_in_the_outer_one() {
+    let ((inner, _), outer) = measure(|| {
+        let a = String::from("a");
+        let (b, inner) = measure(|| a.clone());
+        drop((a, b));
+        (inner, ())
+    });
+    assert_eq!(inner.allocations, 1);
+    assert_eq!(outer.allocations, 2);
+    assert_eq!(outer.deallocations, 2);
+}
+
+#[test]
+fn a_panic_inside_measure_still_restores_the_outer_measurement() {
+    let ((), outer) = measure(|| {
+        let big: Vec<u8> = Vec::with_capacity(1 << 20);
+        let caught = std::panic::catch_unwind(|| measure(|| panic!("inside measure")));
+        assert!(caught.is_err());
+        drop(big);
+    });
+    // Without the restore, the outer count would have started over at the inner `measure`.
+    assert!(outer.bytes_allocated >= 1 << 20, "{outer}");
+    assert!(outer.deallocations >= 1, "{outer}");
+}
@@ -0,0 +1,7 @@
+// No #[global_allocator] here: measure must refuse rather than report zeros.
+
+#[test]
+#[should_panic(expected = "needs `#[global_allocator]")]
+fn measure_panics_without_the_counting_allocator() {
+    lessons::alloc_counter::measure(|| String::from("hello"));
+}
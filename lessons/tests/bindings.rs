@@ -0,0 +1,115 @@
+use lessons::bindings::{Event, Tracer};
+
+#[test]
+fn shadowing_gets_a_new_slot() {
+    let mut t = Tracer::new();
+    let x = 5;
+    t.bind("x", &x);
+    let x = x + 1;
+    t.bind("x", &x);
+    assert_eq!(x, 6);
+
+    let [Event::Let { binding: first, shadows: None, .. }, Event::Let { binding: second, shadows: Some(hidden), value }] =
+        t.events()
+    else {
+        panic!("unexpected events: {:?}", t.events());
+    };
+    assert_eq!(hidden, first);
+    assert_ne!(first.address, second.address);
+    assert_eq!(value, "6");
+    assert_eq!(t.slots().len(), 2);
+}
+
+#[test]
+fn assignment_reuses_the_slot() {
+    let mut t = Tracer::new();
+    let mut x = 5;
+    t.bind_mut("x", &x);
+    x = 6;
+    t.assign("x", &x);
+
+    let [Event::Let { binding, .. }, Event::Assign { binding: assigned, address, value }] = t.events() else {
+        panic!("unexpected events: {:?}", t.events());
+    };
+    assert!(binding.mutable);
+    assert_eq!(assigned, binding);
+    assert_eq!(*address, binding.address);
+    assert_eq!(value, "6");
+    assert_eq!(t.slots(), vec![binding.address]);
+    assert!(t.to_string().contains("same slot, value overwritten"));
+}
+
+#[test]
+fn shadowing_can_change_the_type() {
+    let mut t = Tracer::new();
+    let spaces = "   ";
+    t.bind("spaces", &spaces);
+    let spaces = spaces.len();
+    t.bind("spaces", &spaces);
+
+    let visible = t.visible("spaces").unwrap();
+    assert_eq!(visible.type_name, "usize");
+    assert!(t.to_string().contains("type &str -> usize"), "{t}");
+}
+
+#[test]
+fn leaving_a_block_uncovers_the_outer_binding() {
+    let mut t = Tracer::new();
+    let x = 6;
+    t.bind("x", &x);
+    {
+        t.enter();
+        let x = x * 2;
+        t.bind("x", &x);
+        let y = 1;
+        t.bind("y", &y);
+        assert_eq!(t.visible("x").unwrap().depth, 1);
+        t.exit();
+    }
+
+    assert_eq!(t.visible("x").unwrap().depth, 0);
+    assert!(t.visible("y").is_none());
+    let Some(Event::Exit { depth: 1, dropped, uncovered }) = t.events().last() else {
+        panic!("unexpected events: {:?}", t.events());
+    };
+    assert_eq!(dropped.iter().map(|b| b.name.as_str()).collect::<Vec<_>>(), ["x", "y"]);
+    assert_eq!(uncovered.len(), 1);
+    assert_eq!(uncovered[0].depth, 0);
+}
+
+#[test]
+fn timeline_indents_by_depth() {
+    let mut t = Tracer::new();
+    let x = 1;
+    t.bind("x", &x);
+    t.enter();
+    let x = 2;
+    t.bind("x", &x);
+    t.exit();
+
+    let lines: Vec<String> = t.to_string().lines().map(str::to_string).collect();
+    assert!(lines[0].starts_with("depth  code"));
+    assert!(lines[1].starts_with("0      let x = 1"));
+    assert!(lines[2].starts_with("1      {"));
+    assert!(lines[3].starts_with("1          let x = 2"));
+    assert!(lines[4].starts_with("1      }"));
+    assert!(lines[4].ends_with("x (slot 2) out of scope; x (slot 1) visible again"));
+}
+
+#[test]
+#[should_panic(expected = "no binding `z` in scope")]
+fn assigning_an_unknown_name_panics() {
+    Tracer::new().assign("z", &1);
+}
+
+#[test]
+fn shadowing_in_a_reused_slot_says_so() {
+    let mut t = Tracer::new();
+    let x = 5;
+    // Both bindings at one address, as when the compiler reuses a dead slot.
+    t.bind("x", &x);
+    t.bind("x", &x);
+    let rendered = t.to_string();
+    assert!(rendered.contains("same slot, shadows x (slot 1)"), "{rendered}");
+    assert!(!rendered.contains("new slot"), "{rendered}");
+}
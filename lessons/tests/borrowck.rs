@@ -0,0 +1,149 @@
+use lessons::borrowck::{check, report, MAX_NESTING};
+
+/// Error codes reported for `body`, wrapped in `fn main() { ... }`.
+fn codes_in_main(body: &str) -> Vec<&'static str> {
+    codes(&format!("fn main() {{\n{body}\n}}"))
+}
+
+fn codes(source: &str) -> Vec<&'static str> {
+    check(source).iter().map(|d| d.code.unwrap_or("syntax")).collect()
+}
+
+#[test]
+fn moves_copies_and_borrows_that_compile() {
+    assert_eq!(codes_in_main("let s1 = String::from(\"hello\"); let s2 = s1; print(s2);"), [] as [&str; 0]);
+    assert_eq!(codes_in_main("let x = 5; let y = x; print(x, y);"), [] as [&str; 0]);
+    assert_eq!(
+        codes_in_main("let s = String::from(\"hi\"); let r = &s; let r2 = r; print(r, r2, s);"),
+        [] as [&str; 0]
+    );
+}
+
+#[test]
+fn use_after_move() {
+    assert_eq!(codes_in_main("let s1 = String::from(\"hello\"); let s2 = s1; print(s1);"), ["E0382"]);
+    let takes = "fn main() { let s = String::from(\"hello\"); takes_ownership(s); let t = s; }
+                 fn takes_ownership(some_string: String) { print(some_string); }";
+    assert_eq!(codes(takes), ["E0382"]);
+}
+
+#[test]
+fn one_mutable_or_many_shared_borrows() {
+    assert_eq!(
+        codes_in_main("let mut s = String::from(\"x\"); let r1 = &mut s; let r2 = &mut s; print(r1, r2);"),
+        ["E0499"]
+    );
+    assert_eq!(
+        codes_in_main("let mut s = String::from(\"x\"); let r1 = &s; let r2 = &mut s; print(r1, r2);"),
+        ["E0502"]
+    );
+    assert_eq!(codes_in_main("let mut s = String::from(\"x\"); let r = &mut s; print(s); print(r);"), ["E0502"]);
+    assert_eq!(codes_in_main("let s = String::from(\"x\"); let r = &mut s; print(r);"), ["E0596"]);
+}
+
+#[test]
+fn borrows_end_at_the_last_use() {
+    let nll = "let mut s = String::from(\"x\");
+               let r1 = &s; let r2 = &s; print(r1, r2);
+               let r3 = &mut s; print(r3);";
+    assert_eq!(codes_in_main(nll), [] as [&str; 0]);
+}
+
+#[test]
+fn moving_or_assigning_while_borrowed() {
+    assert_eq!(codes_in_main("let s = String::from(\"x\"); let r = &s; let t = s; print(r);"), ["E0505"]);
+    assert_eq!(codes_in_main("let mut x = 5; let r = &x; x = 6; print(r);"), ["E0506"]);
+    assert_eq!(codes_in_main("let x = 5; x = 6;"), ["E0384"]);
+    assert_eq!(codes_in_main("let x; x = 6; print(x);"), [] as [&str; 0]);
+    assert_eq!(codes_in_main("let x; print(x);"), ["E0381"]);
+}
+
+#[test]
+fn mutable_references_write_through_and_reborrow() {
+    let change = "fn main() { let mut s = String::from(\"hello\"); change(&mut s); print(s); }
+                  fn change(some_string: &mut String) { *some_string = String::from(\"hello, world\"); }";
+    assert_eq!(codes(change), [] as [&str; 0]);
+
+    let reborrow = "fn main() { let mut s = String::from(\"a\"); let r = &mut s; change(r); change(r); }
+                    fn change(some_string: &mut String) { }";
+    assert_eq!(codes(reborrow), [] as [&str; 0]);
+
+    let shared = "fn change(some_string: &String) { *some_string = String::from(\"x\"); }";
+    assert_eq!(codes(shared), ["E0594"]);
+    assert_eq!(codes_in_main("let s = String::from(\"a\"); let r = &s; let t = *r;"), ["E0507"]);
+}
+
+#[test]
+fn dangling_references() {
+    let dangle = "fn dangle() -> &String { let s = String::from(\"hello\"); &s }";
+    assert_eq!(codes(dangle), ["E0106"]);
+    // Two reference parameters: elision can't tell which one is returned.
+    let longest = "fn longest(x: &String, y: &String) -> &String { x }";
+    assert_eq!(codes(longest), ["E0106"]);
+    let rendered = report(longest).unwrap();
+    assert!(rendered.contains("whether it is borrowed from `x` or `y`"), "{rendered}");
+    assert_eq!(codes("fn first(x: &String, n: i32) -> &String { x }"), [] as [&str; 0]);
+    assert_eq!(codes("fn inner(x: &&String) -> &String { *x }"), ["E0106"]);
+
+    let local = "fn first(s: &String) -> &String { let t = String::from(\"x\"); &t }";
+    assert_eq!(codes(local), ["E0515"]);
+    let passthrough = "fn first(s: &String) -> &String { s }";
+    assert_eq!(codes(passthrough), [] as [&str; 0]);
+
+    assert_eq!(codes_in_main("let r; { let x = 5; r = &x; } print(r);"), ["E0597"]);
+    assert_eq!(codes_in_main("let r = { let x = 5; &x };"), ["E0597"]);
+    assert_eq!(codes_in_main("let r; { let x = 5; r = &x; }"), [] as [&str; 0]);
+}
+
+#[test]
+fn calls_are_type_checked() {
+    let program = "fn main() { let s = String::from(\"a\"); takes(&s); takes(s, 1); missing(); }
+                   fn takes(s: String) { }";
+    assert_eq!(codes(program), ["E0308", "E0061", "E0425"]);
+}
+
+#[test]
+fn syntax_errors_have_no_code() {
+    let diagnostics = check("fn main() { let x = ; }");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, None);
+    assert_eq!(diagnostics[0].message, "expected an expression, found `;`");
+}
+
+#[test]
+fn report_renders_like_rustc() {
+    let source = "fn main() {
+    let s1 = String::from(\"hello\");
+    let s2 = s1;
+    print(s1);
+}";
+    let expected = "\
+error[E0382]: borrow of moved value: `s1`
+ --> 4:11
+  |
+3 |     let s2 = s1;
+  |              -- value moved here
+4 |     print(s1);
+  |           ^^ value borrowed here after move
+";
+    assert_eq!(report(source).as_deref(), Some(expected));
+    assert_eq!(report("fn main() { }"), None);
+}
+
+#[test]
+fn deep_nesting_is_an_error_not_a_stack_overflow() {
+    let nested = |depth: usize| format!("fn main() {{ {}{} }}", "{".repeat(depth), "}".repeat(depth));
+    // Each block counts twice: once as an expression, once as a block.
+    assert_eq!(codes(&nested(MAX_NESTING / 2 - 1)), [] as [&str; 0]);
+    let errors = check(&nested(3000));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "expression nested too deeply");
+    let deep_type = format!("fn f(x: {}i32) {{}} fn main() {{}}", "&".repeat(MAX_NESTING + 1));
+    assert_eq!(check(&deep_type)[0].message, "expression nested too deeply");
+}
+
+#[test]
+fn rendered_labels_have_no_trailing_spaces() {
+    let rendered = report("fn main() { let x = 5 }").unwrap();
+    assert!(rendered.lines().all(|line| line == line.trim_end()), "{rendered:?}");
+}
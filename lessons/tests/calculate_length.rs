@@ -0,0 +1,34 @@
+use lessons::{borrowing, ownership};
+
+#[test]
+fn tuple_version_hands_the_same_string_back() {
+    let s1 = String::from("hello");
+    let ptr = s1.as_ptr();
+    let (s2, len) = ownership::calculate_length(s1);
+    assert_eq!((s2.as_str(), len), ("hello", 5));
+    assert_eq!(s2.as_ptr(), ptr, "the String moved in and out without a copy");
+}
+
+#[test]
+fn borrowing_version_leaves_the_caller_owning_the_string() {
+    let s1 = String::from("hello");
+    assert_eq!(borrowing::calculate_length(&s1), 5);
+    assert_eq!(s1, "hello");
+}
+
+#[test]
+fn lengths_are_bytes_not_chars() {
+    let s = String::from("ℤ😻");
+    assert_eq!(borrowing::calculate_length(&s), 7);
+    assert_eq!(ownership::calculate_length(s).1, 7);
+}
+
+#[test]
+fn as_ref_version_accepts_anything_str_like() {
+    let s = String::from("hello");
+    assert_eq!(borrowing::calculate_length_as_ref("hello"), 5);
+    assert_eq!(borrowing::calculate_length_as_ref(&s), 5);
+    assert_eq!(borrowing::calculate_length_as_ref(&s[1..]), 4);
+    assert_eq!(borrowing::calculate_length_as_ref(Box::<str>::from("hey")), 3);
+    assert_eq!(borrowing::calculate_length_as_ref(s), 5);
+}
@@ -0,0 +1,92 @@
+use lessons::borrowing::calculate_length;
+use lessons::chars::{Category, CharInfo, StrReport};
+
+#[test]
+fn the_lesson_chars() {
+    let z = CharInfo::of('z');
+    assert_eq!(
+        (z.code_point_text(), z.utf8.as_slice(), z.utf16.as_slice()),
+        ("U+007A".to_string(), &[0x7a][..], &[0x7a][..])
+    );
+    assert_eq!((z.category, z.is_ascii), (Category::LowercaseLetter, true));
+
+    let double_struck = CharInfo::of('ℤ');
+    assert_eq!(double_struck.code_point, 0x2124);
+    assert_eq!(double_struck.utf8_text(), "e2 84 a4");
+    assert_eq!(double_struck.utf16_text(), "2124");
+    assert_eq!(double_struck.category, Category::UppercaseLetter);
+    assert!(!double_struck.is_ascii);
+
+    let cat = CharInfo::of('😻');
+    assert_eq!(cat.code_point_text(), "U+1F63B");
+    assert_eq!(cat.utf8_text(), "f0 9f 98 bb");
+    assert_eq!(cat.utf16_text(), "d83d de3b", "outside the BMP: a surrogate pair");
+    assert_eq!(cat.category, Category::Symbol);
+}
+
+#[test]
+fn encodings_round_trip() {
+    for c in ['\0', 'A', 'é', 'ℤ', '中', '😻', char::MAX] {
+        let info = CharInfo::of(c);
+        assert_eq!(info.utf8.len(), c.len_utf8());
+        assert_eq!(std::str::from_utf8(&info.utf8).unwrap().chars().next(), Some(c));
+        assert_eq!(char::decode_utf16(info.utf16.iter().copied()).next().unwrap(), Ok(c));
+    }
+}
+
+#[test]
+fn categories() {
+    let cases = [
+        ('A', Category::UppercaseLetter),
+        ('中', Category::OtherLetter),
+        ('7', Category::Number),
+        ('½', Category::Number),
+        (' ', Category::Whitespace),
+        ('\u{3000}', Category::Whitespace),
+        ('\n', Category::Control),
+        ('!', Category::Punctuation),
+        ('+', Category::Symbol),
+        ('—', Category::Punctuation),
+        ('€', Category::Symbol),
+        ('\u{E000}', Category::PrivateUse),
+        ('\u{301}', Category::Mark),
+        ('\u{200B}', Category::Format),
+        ('\u{AD}', Category::Format),
+        ('→', Category::Symbol),
+        ('\u{A4D0}', Category::OtherLetter),
+        ('\u{1D360}', Category::Number),
+        ('\u{0378}', Category::Unknown),
+        // Alphabetic or cased in std, but not letters.
+        ('Ⓐ', Category::Symbol),
+        ('\u{1F130}', Category::Symbol),
+        ('Ⅻ', Category::Number),
+        ('ⅰ', Category::Number),
+        ('ª', Category::OtherLetter),
+        ('ʰ', Category::OtherLetter),
+        ('\u{93F}', Category::LetterLike),
+        ('\u{995}', Category::LetterLike),
+        ('ℤ', Category::UppercaseLetter),
+        ('™', Category::Symbol),
+        // Noncharacters at the end of the private use planes.
+        ('\u{FFFFD}', Category::PrivateUse),
+        ('\u{FFFFE}', Category::Unknown),
+        ('\u{FFFFF}', Category::Unknown),
+        ('\u{10FFFD}', Category::PrivateUse),
+        ('\u{10FFFE}', Category::Unknown),
+        ('\u{10FFFF}', Category::Unknown),
+    ];
+    for (c, category) in cases {
+        assert_eq!(Category::of(c), category, "{c:?}");
+    }
+}
+
+#[test]
+fn len_counts_bytes_and_chars_count_counts_chars() {
+    let s = String::from("zℤ😻");
+    let report = StrReport::of(&s);
+    assert_eq!(report.byte_len(), calculate_length(&s));
+    assert_eq!((report.byte_len(), report.char_count(), report.utf16_len()), (8, 3, 4));
+    let offsets: Vec<usize> = report.chars.iter().map(|(offset, _)| *offset).collect();
+    assert_eq!(offsets, [0, 1, 4]);
+    assert!(report.to_string().starts_with("\"zℤ😻\": len() = 8 bytes, chars().count() = 3"));
+}
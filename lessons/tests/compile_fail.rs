@@ -0,0 +1,104 @@
+// Compile-fail cases
+// Every `.rs` file under `compile_fail/<lesson>/` is a snippet a lesson says the
+// compiler rejects. Each rejected line carries a `//~ ERROR E0xxx` annotation;
+// this test compiles every snippet with `rustc` and checks that it fails with
+// exactly those error codes on exactly those lines.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// (line, error code), e.g. `(5, "E0384")`.
+type Diagnostic = (usize, String);
+
+fn cases() -> Vec<PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("compile_fail");
+    let mut cases = Vec::new();
+    for lesson in fs::read_dir(&root).expect("compile_fail directory") {
+        let lesson = lesson.unwrap().path();
+        if !lesson.is_dir() {
+            continue;
+        }
+        for case in fs::read_dir(&lesson).unwrap() {
+            let case = case.unwrap().path();
+            if case.extension().is_some_and(|ext| ext == "rs") {
+                cases.push(case);
+            }
+        }
+    }
+    cases.sort();
+    cases
+}
+
+fn expected(source: &str) -> BTreeSet<Diagnostic> {
+    source
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let (_, annotation) = line.split_once("//~ ERROR ")?;
+            Some((i + 1, annotation.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Parses `--error-format=short` output: `path:line:col: error[E0384]: message`.
+fn reported(stderr: &str) -> (BTreeSet<Diagnostic>, Vec<String>) {
+    let mut coded = BTreeSet::new();
+    let mut uncoded = Vec::new();
+    for line in stderr.lines() {
+        let Some((location, rest)) = line.split_once(": error") else {
+            continue;
+        };
+        let line_number = location.split(':').nth(1).and_then(|n| n.parse().ok());
+        let code = rest.strip_prefix('[').and_then(|rest| rest.split_once(']')).map(|(code, _)| code.to_string());
+        match (line_number, code) {
+            (Some(line_number), Some(code)) => {
+                coded.insert((line_number, code));
+            }
+            _ => uncoded.push(line.to_string()),
+        }
+    }
+    (coded, uncoded)
+}
+
+fn check(case: &Path, out_dir: &Path) -> Result<(), String> {
+    let source = fs::read_to_string(case).map_err(|e| e.to_string())?;
+    let expected = expected(&source);
+    if expected.is_empty() {
+        return Err("no `//~ ERROR` annotations".to_string());
+    }
+
+    let output = Command::new("rustc")
+        .args(["--edition", "2021", "--error-format=short", "--emit=metadata", "-A", "warnings"])
+        .arg("--out-dir")
+        .arg(out_dir)
+        .arg(case)
+        .output()
+        .map_err(|e| format!("could not run rustc: {e}"))?;
+    if output.status.success() {
+        return Err("compiled, but the lesson says it should not".to_string());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let (reported, uncoded) = reported(&stderr);
+    if reported != expected || !uncoded.is_empty() {
+        return Err(format!("expected {expected:?}, rustc reported {reported:?}\n{stderr}"));
+    }
+    Ok(())
+}
+
+#[test]
+fn lesson_snippets_fail_with_the_documented_error_codes() {
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("compile_fail");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let cases = cases();
+    assert!(!cases.is_empty(), "no compile-fail cases found");
+
+    let failures: Vec<String> = cases
+        .iter()
+        .filter_map(|case| check(case, &out_dir).err().map(|why| format!("{}: {why}", case.display())))
+        .collect();
+    assert!(failures.is_empty(), "\n{}", failures.join("\n\n"));
+}
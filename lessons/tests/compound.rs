@@ -0,0 +1,49 @@
+use lessons::compound::{
+    self, element, first_and_last, flatten, head_tail, index_catching_panic, shape, sum_points, OutOfBounds,
+};
+
+#[test]
+fn get_based_indexing() {
+    let a = [1, 2, 3, 4, 5];
+    assert_eq!(element(&a, 0), Ok(1));
+    assert_eq!(element(&a, 4), Ok(5));
+    assert_eq!(element(&a, 5), Err(OutOfBounds { index: 5, len: 5 }));
+    assert_eq!(element::<i32>(&[], 0).unwrap_err().to_string(), "index out of bounds: the len is 0 but the index is 0");
+}
+
+#[test]
+fn caught_panic_has_the_same_message() {
+    let a = [1, 2, 3, 4, 5];
+    assert_eq!(index_catching_panic(&a, 1), Ok(2));
+    let message = index_catching_panic(&a, 10).unwrap_err();
+    assert_eq!(message, OutOfBounds { index: 10, len: 5 }.to_string());
+}
+
+#[test]
+fn slice_patterns() {
+    assert_eq!(first_and_last::<i32>(&[]), None);
+    assert_eq!(first_and_last(&[7]), Some((&7, &7)));
+    assert_eq!(first_and_last(&[1, 2, 3]), Some((&1, &3)));
+    assert_eq!(shape(&[]), "empty");
+    assert_eq!(shape(&[4]), "one element: 4");
+    assert_eq!(shape(&[4, 5]), "a pair: 4 and 5");
+    assert_eq!(shape(&[1, 2, 3, 4, 5]), "1, then 3 more, then 5");
+    assert_eq!(head_tail(&[1, 2, 3]), Some((&1, &[2, 3][..])));
+    assert_eq!(head_tail::<u8>(&[]), None);
+}
+
+#[test]
+fn nested_destructuring() {
+    assert_eq!(flatten(((500, 6.4), [1, 2, 3], "tup")), (500, 6.4, 1, 2, 3, "tup"));
+    assert_eq!(sum_points(&[(1, 2), (3, 4), (5, 6)]), (9, 12));
+    assert_eq!(sum_points(&[]), (0, 0));
+}
+
+#[test]
+fn repeat_expressions() {
+    let a: [i32; 5] = compound::filled(3);
+    assert_eq!(a, [3; 5]);
+    assert_eq!(a, [3, 3, 3, 3, 3]);
+    let empty: [char; 0] = compound::filled('x');
+    assert!(empty.is_empty());
+}
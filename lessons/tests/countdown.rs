@@ -0,0 +1,158 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use lessons::countdown::{Abort, Clock, Countdown, ManualClock, Outcome, SystemClock, Tick};
+
+/// Runs `countdown` one way or the other and returns its ticks, the outcome
+/// and the clock.
+fn run(mut countdown: Countdown<'_>, with_while: bool) -> (Vec<Tick>, Outcome, ManualClock) {
+    let ticks = Rc::new(RefCell::new(Vec::new()));
+    let log = Rc::clone(&ticks);
+    countdown.on_tick(move |tick| log.borrow_mut().push(*tick));
+    let mut clock = ManualClock::new();
+    let outcome = if with_while { countdown.run_while(&mut clock) } else { countdown.run_for(&mut clock) };
+    let ticks = ticks.borrow().clone();
+    (ticks, outcome, clock)
+}
+
+fn numbers(ticks: &[Tick]) -> Vec<u32> {
+    ticks.iter().map(|t| t.number).collect()
+}
+
+#[test]
+fn counts_down_to_liftoff() {
+    let (ticks, outcome, _) = run(Countdown::new(3), true);
+    assert_eq!(numbers(&ticks), [3, 2, 1]);
+    assert_eq!(outcome, Outcome::Liftoff);
+    assert_eq!(ticks.iter().map(|t| t.index).collect::<Vec<_>>(), [0, 1, 2]);
+}
+
+#[test]
+fn start_and_step_are_configurable() {
+    assert_eq!(numbers(&run(Countdown::new(10).step(2), false).0), [10, 8, 6, 4, 2]);
+    assert_eq!(numbers(&run(Countdown::new(7).step(3), true).0), [7, 4, 1]);
+    assert_eq!(numbers(&run(Countdown::new(2).step(5), true).0), [2]);
+    assert_eq!(run(Countdown::new(0), false), (vec![], Outcome::Liftoff, ManualClock::new()));
+}
+
+#[test]
+fn while_and_for_agree() {
+    for start in 0..=30 {
+        for step in 1..=7 {
+            let make = || Countdown::new(start).step(step).delay(Duration::from_millis(250));
+            let expected: Vec<u32> = make().numbers().collect();
+            let by_while = run(make(), true);
+            let by_for = run(make(), false);
+            assert_eq!(by_while, by_for, "start {start}, step {step}");
+            assert_eq!(numbers(&by_while.0), expected, "start {start}, step {step}");
+        }
+    }
+}
+
+#[test]
+fn delay_goes_through_the_clock() {
+    let (ticks, _, clock) = run(Countdown::new(10).delay(Duration::from_secs(1)), true);
+    let at: Vec<u64> = ticks.iter().map(|t| t.at.as_secs()).collect();
+    assert_eq!(at, (0..10).collect::<Vec<_>>());
+    // No wait after the last tick: liftoff follows it straight away.
+    assert_eq!(clock.now, Duration::from_secs(9));
+    assert_eq!(clock.sleeps, vec![Duration::from_secs(1); 9]);
+}
+
+#[test]
+fn abort_stops_before_the_next_tick() {
+    for with_while in [true, false] {
+        let abort = Abort::new();
+        let trip = abort.clone();
+        let mut countdown = Countdown::new(5).abort_on(abort);
+        countdown.on_tick(move |tick| {
+            if tick.number == 3 {
+                trip.abort();
+            }
+        });
+        let (ticks, outcome, _) = run(countdown, with_while);
+        assert_eq!(numbers(&ticks), [5, 4, 3]);
+        assert_eq!(outcome, Outcome::Aborted { next: 2 });
+    }
+}
+
+#[test]
+fn abort_on_the_last_tick_cancels_liftoff() {
+    let abort = Abort::new();
+    let trip = abort.clone();
+    let mut countdown = Countdown::new(2).abort_on(abort);
+    countdown.on_tick(move |tick| {
+        if tick.number == 1 {
+            trip.abort();
+        }
+    });
+    assert_eq!(run(countdown, false).1, Outcome::Aborted { next: 0 });
+}
+
+#[test]
+fn abort_cuts_a_sleep_short() {
+    for with_while in [true, false] {
+        let abort = Abort::new();
+        let mut countdown = Countdown::new(5).delay(Duration::from_secs(10)).abort_on(abort.clone());
+        let ticks = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&ticks);
+        countdown.on_tick(move |tick| log.borrow_mut().push(tick.number));
+        // Pressed 2.5 seconds into the wait after 4.
+        let mut clock = ManualClock::new().abort_at(Duration::from_millis(12_500));
+        let outcome = if with_while { countdown.run_while(&mut clock) } else { countdown.run_for(&mut clock) };
+        assert_eq!(*ticks.borrow(), [5, 4]);
+        assert_eq!(outcome, Outcome::Aborted { next: 3 });
+        assert_eq!(clock.now, Duration::from_millis(12_500));
+        assert_eq!(clock.sleeps, [Duration::from_secs(10), Duration::from_millis(2_500)]);
+        assert!(abort.is_aborted());
+    }
+}
+
+#[test]
+fn system_clock_wakes_up_on_abort() {
+    let abort = Abort::new();
+    let trip = abort.clone();
+    let presser = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        trip.abort();
+    });
+    let started = Instant::now();
+    SystemClock::new().sleep(Duration::from_secs(60), &abort);
+    presser.join().unwrap();
+    assert!(started.elapsed() < Duration::from_secs(30), "slept {:?}", started.elapsed());
+}
+
+#[test]
+fn aborted_before_starting() {
+    let abort = Abort::new();
+    abort.abort();
+    let (ticks, outcome, clock) = run(Countdown::new(3).abort_on(abort), true);
+    assert!(ticks.is_empty());
+    assert_eq!(outcome, Outcome::Aborted { next: 3 });
+    assert!(clock.sleeps.is_empty());
+}
+
+#[test]
+fn hooks_run_in_order() {
+    let log = RefCell::new(Vec::new());
+    let mut countdown = Countdown::new(2);
+    countdown.on_tick(|tick| log.borrow_mut().push(format!("a{}", tick.number)));
+    countdown.on_tick(|tick| log.borrow_mut().push(format!("b{}", tick.number)));
+    countdown.run_for(&mut ManualClock::new());
+    drop(countdown);
+    assert_eq!(log.into_inner(), ["a2", "b2", "a1", "b1"]);
+}
+
+#[test]
+#[should_panic(expected = "never reaches liftoff")]
+fn zero_step_is_rejected() {
+    let _ = Countdown::new(3).step(0);
+}
+
+#[test]
+fn outcomes_read_like_the_lesson() {
+    assert_eq!(Outcome::Liftoff.to_string(), "LIFTOFF!!!");
+    assert_eq!(Outcome::Aborted { next: 2 }.to_string(), "aborted before 2");
+    assert_eq!(Outcome::Aborted { next: 0 }.to_string(), "aborted before liftoff");
+}
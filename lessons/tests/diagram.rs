@@ -0,0 +1,64 @@
+use lessons::diagram::{Diagram, StringParts};
+
+fn parts(ptr: usize, contents: &str, capacity: usize) -> StringParts {
+    StringParts { ptr, len: contents.len(), capacity, bytes: contents.as_bytes().to_vec() }
+}
+
+#[test]
+fn string_parts_reads_the_real_fields() {
+    let mut s = String::with_capacity(16);
+    s.push_str("hello");
+    let fields = StringParts::of(&s);
+    assert_eq!(fields.ptr, s.as_ptr() as usize);
+    assert_eq!((fields.len, fields.capacity), (5, 16));
+    assert_eq!(fields.bytes, b"hello");
+}
+
+#[test]
+fn a_move_leaves_both_bindings_pointing_at_one_buffer() {
+    let s1 = String::from("hello");
+    let before = StringParts::of(&s1);
+    let s2 = s1;
+    assert_eq!(before.ptr, s2.as_ptr() as usize, "a move doesn't copy the heap data");
+
+    let drawn = Diagram::new().moved("s1", before).string("s2", &s2).to_string();
+    assert!(drawn.contains("s1 (moved: no longer valid)"), "{drawn}");
+    assert_eq!(drawn.matches("--> #1").count(), 2, "{drawn}");
+    assert!(!drawn.contains("#2"), "{drawn}");
+}
+
+#[test]
+fn a_clone_gets_its_own_buffer() {
+    let s1 = String::from("hello");
+    let s2 = s1.clone();
+    assert_ne!(s1.as_ptr(), s2.as_ptr());
+
+    let drawn = Diagram::new().string("s1", &s1).string("s2", &s2).to_string();
+    assert!(drawn.contains("--> #1") && drawn.contains("--> #2"), "{drawn}");
+    assert!(!drawn.contains("moved"), "{drawn}");
+}
+
+#[test]
+fn renders_fields_bytes_and_spare_capacity() {
+    let drawn = Diagram::new().moved("s", parts(0x1000, "hi", 4)).to_string();
+    let expected = "\
+stack                                       heap
+
+s (moved: no longer valid)                  #1 at 0x0000000000001000
++----------+--------------------+           +-------+-------+
+| ptr      | 0x0000000000001000 | --> #1    | index | value |
+| len      | 2                  |           +-------+-------+
+| capacity | 4                  |           | 0     | h     |
++----------+--------------------+           | 1     | i     |
+                                            +-------+-------+
+                                            (2 more bytes of capacity unused)
+";
+    assert_eq!(drawn, expected);
+}
+
+#[test]
+fn an_empty_string_has_no_heap_buffer() {
+    let drawn = Diagram::new().string("s", &String::new()).to_string();
+    assert!(drawn.contains("(no heap buffer)"), "{drawn}");
+    assert!(!drawn.contains("#1"), "{drawn}");
+}
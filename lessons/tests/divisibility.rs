@@ -0,0 +1,156 @@
+use lessons::divisibility::{
+    all_matches_if, all_matches_match, book_if_chain, book_match, book_rules, first_match_if, first_match_match,
+    Classifier, Mode, Rule, RuleError,
+};
+
+/// A small xorshift generator, so the property tests are reproducible
+/// without pulling in a crate.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn rules(&mut self) -> Vec<Rule> {
+        let len = self.below(7);
+        (0..len)
+            .map(|i| {
+                let divisor = 1 + self.below(12);
+                Rule::new(divisor, &format!("r{i}"))
+            })
+            .collect()
+    }
+
+    fn number(&mut self) -> u64 {
+        // Mostly small numbers, where divisors overlap, and now and then a huge one.
+        if self.below(10) == 0 {
+            self.next()
+        } else {
+            self.below(1000)
+        }
+    }
+}
+
+const CASES: usize = 5_000;
+
+#[test]
+fn if_and_match_agree_on_the_book_chain() {
+    for number in 0..=10_000 {
+        assert_eq!(book_if_chain(number), book_match(number), "{number}");
+    }
+    assert_eq!(book_if_chain(u64::MAX), book_match(u64::MAX));
+}
+
+#[test]
+fn classifier_reproduces_the_book_chain() {
+    let classifier = Classifier::new(book_rules(), Mode::FirstMatch).unwrap();
+    for number in 0..=1_000 {
+        let c = classifier.classify(number);
+        let expected = match c.labels.as_slice() {
+            [] => format!("number is {}", classifier.fallback()),
+            [label] => format!("number is {label}"),
+            more => panic!("first-match gave {more:?}"),
+        };
+        assert_eq!(book_if_chain(number), expected, "{number}");
+    }
+}
+
+#[test]
+fn first_match_if_and_match_agree() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..CASES {
+        let rules = rng.rules();
+        let number = rng.number();
+        assert_eq!(first_match_if(&rules, number), first_match_match(&rules, number), "{rules:?} {number}");
+    }
+}
+
+#[test]
+fn all_matches_if_and_match_agree() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..CASES {
+        let rules = rng.rules();
+        let number = rng.number();
+        assert_eq!(all_matches_if(&rules, number), all_matches_match(&rules, number), "{rules:?} {number}");
+    }
+}
+
+#[test]
+fn first_match_is_the_head_of_all_matches() {
+    let mut rng = Rng(0xdead_beef_cafe_f00d);
+    for _ in 0..CASES {
+        let rules = rng.rules();
+        let number = rng.number();
+        let all = all_matches_if(&rules, number);
+        assert_eq!(first_match_if(&rules, number), all.first().copied(), "{rules:?} {number}");
+        assert!(all.iter().all(|rule| number.is_multiple_of(rule.divisor)));
+        let matching = rules.iter().filter(|rule| number.is_multiple_of(rule.divisor)).count();
+        assert_eq!(all.len(), matching);
+    }
+}
+
+#[test]
+fn all_matches_keeps_rule_order() {
+    let classifier = Classifier::new(book_rules(), Mode::AllMatches).unwrap();
+    assert_eq!(classifier.classify(12).labels, ["divisible by 4", "divisible by 3", "divisible by 2"]);
+    assert_eq!(classifier.classify(6).labels, ["divisible by 3", "divisible by 2"]);
+    assert!(classifier.classify(7).labels.is_empty());
+}
+
+#[test]
+fn fizzbuzz_depends_on_rule_order() {
+    let fizzbuzz = vec![Rule::new(15, "FizzBuzz"), Rule::new(3, "Fizz"), Rule::new(5, "Buzz")];
+    let classifier = Classifier::new(fizzbuzz, Mode::FirstMatch).unwrap();
+    assert_eq!(classifier.classify(30).labels, ["FizzBuzz"]);
+
+    let backwards = vec![Rule::new(3, "Fizz"), Rule::new(5, "Buzz"), Rule::new(15, "FizzBuzz")];
+    let classifier = Classifier::new(backwards, Mode::FirstMatch).unwrap();
+    assert_eq!(classifier.classify(30).labels, ["Fizz"]);
+}
+
+#[test]
+fn ranges() {
+    let classifier = Classifier::new(book_rules(), Mode::FirstMatch).unwrap();
+    let classified: Vec<_> = classifier.classify_range(5..=8).collect();
+    assert_eq!(classified.iter().map(|c| c.number).collect::<Vec<_>>(), [5, 6, 7, 8]);
+    assert_eq!(classified[1].labels, ["divisible by 3"]);
+
+    let table = classifier.table(9..=10);
+    assert_eq!(table, "number  labels\n     9  divisible by 3\n    10  divisible by 2\n");
+    assert_eq!(classifier.table(1234567..=1234568).lines().next(), Some("number   labels"));
+    #[allow(clippy::reversed_empty_ranges)] // the empty range is the point
+    let empty = classifier.table(5..=4);
+    assert_eq!(empty, "number  labels\n");
+
+    // Nothing is collected up front, so the whole u64 range is fine to start on.
+    let mut huge = classifier.classify_range(0..=u64::MAX).skip(12);
+    assert_eq!(huge.next().map(|c| c.labels), Some(vec!["divisible by 4"]));
+}
+
+#[test]
+fn fallback_lists_the_divisors() {
+    let fallback = |divisors: &[u64]| {
+        let rules = divisors.iter().map(|&d| Rule::new(d, "")).collect();
+        Classifier::new(rules, Mode::FirstMatch).unwrap().fallback()
+    };
+    assert_eq!(fallback(&[4, 3, 2]), "not divisible by 4, 3, or 2");
+    assert_eq!(fallback(&[4, 2]), "not divisible by 4 or 2");
+    assert_eq!(fallback(&[7]), "not divisible by 7");
+}
+
+#[test]
+fn parsing_rules() {
+    assert_eq!("15:FizzBuzz".parse(), Ok(Rule::new(15, "FizzBuzz")));
+    assert_eq!("7".parse(), Ok(Rule::new(7, "divisible by 7")));
+    assert_eq!("0:never".parse::<Rule>(), Err(RuleError::ZeroDivisor));
+    assert_eq!("x:y".parse::<Rule>(), Err(RuleError::BadDivisor("x".to_string())));
+    assert_eq!(Classifier::new(vec![Rule::new(0, "zero")], Mode::AllMatches), Err(RuleError::ZeroDivisor));
+}
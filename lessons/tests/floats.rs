@@ -0,0 +1,82 @@
+use lessons::floats::{self, division_rows, exact, precision_rows, special_values, FloatBits, Kind};
+
+#[test]
+fn f32_and_f64_disagree_where_f32_runs_out_of_bits() {
+    let rows = precision_rows();
+    let row = |expr: &str| rows.iter().find(|row| row.expr == expr).unwrap().clone();
+    assert_eq!((row("0.1 + 0.2").f32.as_str(), row("0.1 + 0.2").f64.as_str()), ("0.3", "0.30000000000000004"));
+    assert_eq!(row("0.1 + 0.2 == 0.3").f64, "false");
+    assert_eq!(row("16777217.0").f32, "16777216.0", "2^24 + 1 needs 25 significant bits");
+    assert_eq!(row("95.5 - 4.3").f64, "91.2");
+    assert_eq!(exact(0.1), "0.1000000000000000055511151231257827021181583404541015625");
+    assert_eq!(exact(1.0), "1");
+    assert_eq!(exact(-0.0), "-0");
+    assert_eq!(exact(2.5e-3), "0.0025000000000000000520417042793042128323577344417572021484375");
+    // 2^-1074: every one of its 1074 places is needed.
+    let smallest = exact(f64::from_bits(1));
+    assert_eq!(smallest.len(), "0.".len() + 1074);
+    assert!(smallest.ends_with("625"));
+}
+
+#[test]
+fn bit_fields_reassemble_the_value() {
+    for x in [91.2, -0.1, 1.0, f64::MIN_POSITIVE, 5e-324, f64::MAX] {
+        let bits = FloatBits::of_f64(x);
+        let back = (bits.sign << 63) | (bits.exponent << 52) | bits.mantissa;
+        assert_eq!(f64::from_bits(back), x);
+    }
+    let one = FloatBits::of_f32(1.0);
+    assert_eq!((one.sign, one.exponent, one.mantissa, one.unbiased_exponent()), (0, 127, 0, Some(0)));
+    let minus_two = FloatBits::of_f64(-2.0);
+    assert_eq!((minus_two.sign, minus_two.unbiased_exponent()), (1, Some(1)));
+}
+
+#[test]
+fn kinds_from_the_exponent_field() {
+    assert_eq!(FloatBits::of_f64(0.0).kind(), Kind::Zero);
+    assert_eq!(FloatBits::of_f64(-0.0).kind(), Kind::Zero);
+    assert_eq!(FloatBits::of_f64(5e-324).kind(), Kind::Subnormal);
+    assert_eq!(FloatBits::of_f64(5e-324).unbiased_exponent(), Some(-1022));
+    assert_eq!(FloatBits::of_f64(1.5).kind(), Kind::Normal);
+    assert_eq!(FloatBits::of_f32(f32::NEG_INFINITY).kind(), Kind::Infinite);
+    assert_eq!(FloatBits::of_f32(f32::NAN).kind(), Kind::Nan);
+    assert_eq!(FloatBits::of_f64(f64::NAN).unbiased_exponent(), None);
+}
+
+#[test]
+fn nan_and_infinity_rules() {
+    let values = special_values();
+    let value = |expr: &str| values.iter().find(|(e, _)| *e == expr).unwrap().1.clone();
+    assert_eq!(value("0.0 / 0.0"), "NaN");
+    assert_eq!(value("1.0 / -0.0"), "-inf");
+    assert_eq!(value("f64::NAN == f64::NAN"), "false");
+    assert_eq!(value("f64::NAN.partial_cmp(&1.0)"), "None");
+    assert_eq!(value("f64::NAN.max(1.0)"), "1.0");
+    assert_eq!(value("-0.0 == 0.0"), "true");
+    assert_eq!(value("f64::NAN as i32"), "0", "float-to-int casts saturate, and NaN becomes 0");
+}
+
+#[test]
+fn euclidean_division_keeps_the_remainder_non_negative() {
+    for row in division_rows() {
+        assert_eq!(row.a, row.b * row.quotient + row.remainder);
+        assert_eq!(row.a, row.b * row.div_euclid + row.rem_euclid);
+        assert!(row.rem_euclid >= 0);
+        assert!(row.remainder == 0 || row.remainder.signum() == row.a.signum());
+    }
+    let lesson = division_rows()[0];
+    assert_eq!((lesson.a, lesson.b, lesson.quotient, lesson.div_euclid), (-5, 3, -1, -2));
+}
+
+#[test]
+fn report_has_every_section() {
+    let report = floats::report();
+    for heading in
+        ["== f32 vs f64 ==", "== bit layout ==", "== infinity, NaN and -0.0 ==", "== integer division and remainder =="]
+    {
+        assert!(report.contains(heading), "{heading}");
+    }
+    assert!(floats::describe_f64(91.2).contains("exponent 10000000101"));
+    assert!(floats::describe_f64(91.2).starts_with("91.2_f64 = 0x4056_cccc_cccc_cccd\n"));
+    assert!(floats::describe_f32(91.2).starts_with("91.2_f32 = 0x42b6_6666\n"));
+}
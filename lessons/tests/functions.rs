@@ -0,0 +1,17 @@
+use lessons::functions::{another_function_message, five, plus_one};
+
+#[test]
+fn five_returns_five() {
+    assert_eq!(five(), 5);
+}
+
+#[test]
+fn plus_one_adds_one() {
+    assert_eq!(plus_one(5), 6);
+    assert_eq!(plus_one(-1), 0);
+}
+
+#[test]
+fn another_function_prints_value_and_unit_label() {
+    assert_eq!(another_function_message(5, 'h'), "Another function.value is: 5h");
+}
@@ -0,0 +1,158 @@
+// Golden output
+// Runs every example of every lesson binary and compares its stdout with the
+// checked-in file `golden/<lesson>/<example>.stdout`.
+//
+// After an intended change to what an example prints, regenerate the files:
+//
+//     UPDATE_GOLDEN=1 cargo test --test golden
+//
+// and review the diff before committing it.
+//
+// Heap addresses change from run to run, so every 16-digit `0x…` address is
+// masked before comparing. Hex that isn't an address, like the float lab's
+// bit patterns, is printed in `_`-separated groups so it's still checked. Source locations such as `ownership.rs:265` move
+// with every edit to the lesson, so their line numbers are masked too;
+// tests/traced.rs checks that they're right. Examples whose output depends on
+// the pointer width are recorded on a 64-bit target and skipped elsewhere.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const LESSONS: &[(&str, &str)] = &[
+    ("control_flow", env!("CARGO_BIN_EXE_control_flow")),
+    ("functions", env!("CARGO_BIN_EXE_functions")),
+    ("ownership", env!("CARGO_BIN_EXE_ownership")),
+    ("references_borrowing", env!("CARGO_BIN_EXE_references_borrowing")),
+    ("slices", env!("CARGO_BIN_EXE_slices")),
+    ("variables_data_types", env!("CARGO_BIN_EXE_variables_data_types")),
+];
+
+/// Examples that print `isize`/`usize` bounds, as (lesson, example).
+const POINTER_WIDTH_DEPENDENT: &[(&str, &str)] =
+    &[("variables_data_types", "integer-ranges"), ("variables_data_types", "integer-ranges-markdown")];
+
+fn updating() -> bool {
+    std::env::var_os("UPDATE_GOLDEN").is_some_and(|v| v != "0")
+}
+
+fn golden_dir(lesson: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("golden").join(lesson)
+}
+
+fn stdout(bin: &str, args: &[&str]) -> String {
+    let output = Command::new(bin).args(args).output().expect("run lesson binary");
+    assert!(output.status.success(), "{bin} {args:?} failed: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).expect("stdout is UTF-8")
+}
+
+/// Replaces `0x` followed by 16 hex digits with a fixed mask.
+fn mask_addresses(text: &str) -> String {
+    const DIGITS: usize = 16;
+    let mut masked = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("0x") {
+        masked.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let hex = after.bytes().take_while(u8::is_ascii_hexdigit).count();
+        if hex == DIGITS {
+            masked.push_str("0x");
+            masked.push_str(&"?".repeat(DIGITS));
+        } else {
+            masked.push_str(&rest[start..start + 2 + hex]);
+        }
+        rest = &after[hex..];
+    }
+    masked.push_str(rest);
+    masked
+}
+
+/// Replaces the line number in `file.rs:LINE` with a fixed mask.
+fn mask_line_numbers(text: &str) -> String {
+    let mut masked = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(".rs:") {
+        masked.push_str(&rest[..start + 4]);
+        let after = &rest[start + 4..];
+        let digits = after.bytes().take_while(u8::is_ascii_digit).count();
+        masked.push_str(if digits > 0 { "LINE" } else { "" });
+        rest = &after[digits..];
+    }
+    masked.push_str(rest);
+    masked
+}
+
+fn example_names(bin: &str) -> Vec<String> {
+    // Commands, if any, are listed after a blank line.
+    stdout(bin, &["--list"])
+        .lines()
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Golden files on disk that no longer belong to an example.
+fn stale_files(dir: &Path, names: &[String]) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let known: BTreeSet<String> = names.iter().map(|name| format!("{name}.stdout")).collect();
+    let mut stale: Vec<PathBuf> = entries
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let file_name = path.file_name().unwrap().to_string_lossy();
+            !known.contains(file_name.as_ref())
+        })
+        .collect();
+    stale.sort();
+    stale
+}
+
+#[test]
+fn every_example_matches_its_golden_output() {
+    let update = updating();
+    let mut failures = Vec::new();
+
+    for &(lesson, bin) in LESSONS {
+        let dir = golden_dir(lesson);
+        let names = example_names(bin);
+        assert!(!names.is_empty(), "{lesson} lists no examples");
+
+        for name in &names {
+            if !cfg!(target_pointer_width = "64") && POINTER_WIDTH_DEPENDENT.contains(&(lesson, name.as_str())) {
+                continue;
+            }
+            let actual = mask_line_numbers(&mask_addresses(&stdout(bin, &[name])));
+            let path = dir.join(format!("{name}.stdout"));
+            if update {
+                fs::create_dir_all(&dir).unwrap();
+                fs::write(&path, &actual).unwrap();
+                continue;
+            }
+            match fs::read_to_string(&path) {
+                Ok(expected) if expected == actual => {}
+                Ok(expected) => failures.push(format!(
+                    "{lesson} {name}: output changed\n--- expected ({})\n{expected}--- actual\n{actual}",
+                    path.display()
+                )),
+                Err(_) => failures.push(format!("{lesson} {name}: missing {}", path.display())),
+            }
+        }
+
+        for path in stale_files(&dir, &names) {
+            if update {
+                fs::remove_file(&path).unwrap();
+            } else {
+                failures.push(format!("{lesson}: stale golden file {}", path.display()));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "\n{}\n\nIf the change is intended, rerun with UPDATE_GOLDEN=1.",
+        failures.join("\n\n")
+    );
+}
@@ -0,0 +1,104 @@
+use lessons::integers::literal::{parse, Form, Literal, Value};
+use lessons::integers::IntType;
+
+fn ok(text: &str) -> Literal {
+    parse(text).unwrap_or_else(|e| panic!("{text}: {e}"))
+}
+
+#[test]
+fn every_form_in_the_lesson_table() {
+    let cases = [
+        ("98_222", Form::Decimal, 98_222),
+        ("0xff", Form::Hex, 0xff),
+        ("0o77", Form::Octal, 0o77),
+        ("0b1111_0000", Form::Binary, 0b1111_0000),
+    ];
+    for (text, form, value) in cases {
+        let literal = ok(text);
+        assert_eq!((literal.form, literal.ty, literal.suffixed), (form, IntType::I32, false), "{text}");
+        assert_eq!(literal.value, Value::Signed(value), "{text}");
+    }
+    let byte = ok("b'A'");
+    assert_eq!((byte.form, byte.ty, byte.value), (Form::Byte, IntType::U8, Value::Unsigned(65)));
+}
+
+#[test]
+fn suffixes_set_the_type() {
+    assert_eq!(ok("57u8").ty, IntType::U8);
+    assert_eq!(ok("57_u8").value, Value::Unsigned(57));
+    assert_eq!(ok("0xffu16").value, Value::Unsigned(255));
+    assert_eq!(ok("0b1i128").ty, IntType::I128);
+    assert_eq!(ok("1usize").ty, IntType::Usize);
+    assert!(ok("1usize").suffixed);
+    assert_eq!(ok("340282366920938463463374607431768211455u128").value, Value::Unsigned(u128::MAX));
+}
+
+#[test]
+fn byte_escapes() {
+    assert_eq!(ok(r"b'\n'").value, Value::Unsigned(10));
+    assert_eq!(ok(r"b'\x7f'").value, Value::Unsigned(0x7f));
+    assert_eq!(ok(r"b'\''").value, Value::Unsigned(39));
+    assert_eq!(parse("b'ℤ'").unwrap_err().message, "non-ASCII character in byte literal");
+    assert_eq!(parse("b'ab'").unwrap_err().message, "character literal may only contain one codepoint");
+}
+
+#[test]
+fn negative_literals_reach_min() {
+    assert_eq!(ok("-128i8").value, Value::Signed(-128));
+    assert_eq!(ok("-170141183460469231731687303715884105728i128").value, Value::Signed(i128::MIN));
+    let error = parse("-129i8").unwrap_err();
+    assert_eq!(
+        error.note.as_deref(),
+        Some("the literal `-129i8` does not fit into the type `i8` whose range is `-128..=127`")
+    );
+    assert_eq!(error.help.as_deref(), Some("consider using the type `i16` instead"));
+    let error = parse("-1u8").unwrap_err();
+    assert_eq!((error.code, error.message.as_str()), (Some("E0600"), "cannot apply unary operator `-` to type `u8`"));
+}
+
+// The expected messages are copied from rustc's output for the same literals.
+#[test]
+fn out_of_range_like_rustc() {
+    let error = parse("256u8").unwrap_err();
+    assert_eq!(error.to_string(), "error: literal out of range for `u8`\n  = note: the literal `256u8` does not fit into the type `u8` whose range is `0..=255`\n");
+
+    let error = parse("0x1ff_u8").unwrap_err();
+    assert_eq!(
+        error.note.as_deref(),
+        Some("the literal `0x1ff_u8` (decimal `511`) does not fit into the type `u8` and will become `255u8`")
+    );
+    assert_eq!(error.help.as_deref(), Some("consider using the type `u16` instead"));
+
+    let error = parse("0xFFi8").unwrap_err();
+    assert_eq!(
+        error.note.as_deref(),
+        Some("the literal `0xFFi8` (decimal `255`) does not fit into the type `i8` and will become `-1i8`")
+    );
+    assert_eq!(error.help.as_deref(), Some("consider using the type `u8` instead"));
+
+    let error = parse("0b1_0000_0000u8").unwrap_err();
+    assert!(error.note.unwrap().ends_with("will become `0u8`"));
+
+    let error = parse("0o777i8").unwrap_err();
+    assert_eq!(error.help.as_deref(), Some("consider using the type `i16` instead"));
+
+    let error = parse("3000000000").unwrap_err();
+    assert_eq!(error.message, "literal out of range for `i32`");
+    assert_eq!(error.help.as_deref(), Some("consider using the type `u32` instead"));
+
+    assert_eq!(
+        parse("340282366920938463463374607431768211456u128").unwrap_err().message,
+        "integer literal is too large"
+    );
+}
+
+#[test]
+fn malformed_literals() {
+    assert_eq!(parse("0x").unwrap_err().code, Some("E0768"));
+    assert_eq!(parse("0o78").unwrap_err().message, "invalid digit for a base 8 literal");
+    assert_eq!(parse("0b102").unwrap_err().message, "invalid digit for a base 2 literal");
+    assert_eq!(parse("5xyz").unwrap_err().message, "invalid suffix `xyz` for number literal");
+    assert_eq!(parse("2.5").unwrap_err().message, "`2.5` is a float literal, not an integer literal");
+    assert_eq!(parse("1f64").unwrap_err().message, "`1f64` is a float literal, not an integer literal");
+    assert_eq!(parse("abc").unwrap_err().message, "expected an integer literal, found `abc`");
+}
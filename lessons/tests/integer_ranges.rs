@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::Path;
+
+use lessons::integers::ranges::{self, bits_from_max, formula, min_max, range_text, superscript, Format};
+use lessons::integers::IntType;
+
+#[test]
+fn ranges_follow_the_power_of_two_formulas() {
+    for ty in IntType::ALL {
+        let n = bits_from_max(ty);
+        assert_eq!(n, ty.bits(), "{ty}");
+        let (min, max) = min_max(ty);
+        if ty.is_signed() {
+            assert_eq!(max, (1u128 << (n - 1)) - 1, "{ty}");
+            assert_eq!(min, -(max as i128) - 1, "{ty}");
+        } else {
+            assert_eq!(min, 0);
+            assert_eq!(max, u128::MAX >> (128 - n), "{ty}");
+        }
+    }
+}
+
+#[test]
+fn formulas_use_superscripts() {
+    assert_eq!(superscript("n-1"), "ⁿ⁻¹");
+    assert_eq!(formula(IntType::I8), "-(2⁷) to 2⁷ - 1");
+    assert_eq!(formula(IntType::U8), "0 to 2⁸ - 1");
+    assert_eq!(formula(IntType::I128), "-(2¹²⁷) to 2¹²⁷ - 1");
+    assert_eq!(range_text(IntType::I8), "-128 to 127");
+    assert_eq!(range_text(IntType::U128), "0 to 340282366920938463463374607431768211455");
+    assert_eq!(range_text(IntType::Usize), format!("0 to {}", usize::MAX));
+}
+
+#[test]
+fn both_formats_have_a_row_per_size() {
+    let terminal = ranges::table(Format::Terminal);
+    assert_eq!(terminal.lines().count(), 7);
+    let row: Vec<&str> = terminal.lines().nth(1).unwrap().split_whitespace().collect();
+    assert_eq!(row, ["8-bit", "i8", "-128", "to", "127", "u8", "0", "to", "255"]);
+    assert!(terminal.contains(&format!("arch ({}-bit)", usize::BITS)));
+
+    let markdown = ranges::table(Format::Markdown);
+    let lines: Vec<&str> = markdown.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[0], "| Length | Signed | Range | Unsigned | Range |");
+    assert_eq!(lines[1], "|---|---|---|---|---|");
+    assert_eq!(lines[2], "| 8-bit | `i8` | -128 to 127 | `u8` | 0 to 255 |");
+}
+
+/// The comment lines of the data-types lesson from the paragraph after
+/// `// Data Types` up to the line starting `// Additionally`.
+fn lesson_paragraph() -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/bin/variables_data_types.rs");
+    let source = fs::read_to_string(path).unwrap();
+    let mut lines = source.lines().skip_while(|line| *line != "// Data Types").skip(1);
+    let mut paragraph = String::new();
+    for line in lines.by_ref().take_while(|line| !line.starts_with("// Additionally")) {
+        let text = line.strip_prefix("// ").unwrap_or_else(|| panic!("not a lesson comment: {line:?}"));
+        paragraph.push_str(text.trim_end());
+        paragraph.push('\n');
+    }
+    paragraph
+}
+
+#[test]
+fn lesson_text_matches_the_constants() {
+    assert_eq!(lesson_paragraph(), ranges::lesson_notes(), "update the Data Types notes in variables_data_types.rs");
+}
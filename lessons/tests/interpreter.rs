@@ -0,0 +1,217 @@
+use lessons::interpreter::{check, report, run, Error, Type, Value, FUEL, MAX_NESTING};
+
+fn value(source: &str) -> Value {
+    run(source).unwrap_or_else(|e| panic!("{source:?} failed:\n{}", e.render(source))).value
+}
+
+/// The error code `source` is rejected with, or "syntax" for a parse error.
+fn code(source: &str) -> &'static str {
+    match check(source) {
+        Ok(ty) => panic!("{source:?} type checked as `{ty}`"),
+        Err(diagnostic) => diagnostic.code.unwrap_or("syntax"),
+    }
+}
+
+#[test]
+fn if_is_an_expression() {
+    assert_eq!(value("let condition = true; let number = if condition { 5 } else { 6 }; number"), Value::Int(5));
+    assert_eq!(value("let condition = false; if condition { 5 } else { 6 }"), Value::Int(6));
+    let else_if = "let number = 6;
+        if number % 4 == 0 { \"divisible by 4\" }
+        else if number % 3 == 0 { \"divisible by 3\" }
+        else if number % 2 == 0 { \"divisible by 2\" }
+        else { \"not divisible by 4, 3, or 2\" }";
+    assert_eq!(value(else_if), Value::Str("divisible by 3".to_string()));
+    assert_eq!(check(else_if), Ok(Type::Str));
+}
+
+#[test]
+fn blocks_are_expressions() {
+    assert_eq!(value("let y = { let x = 3; x + 1 }; y"), Value::Int(4));
+    // A `;` after the last expression turns the value into `()`.
+    assert_eq!(value("let y = { let x = 3; x + 1; }; y"), Value::Unit);
+    assert_eq!(check("let y = { let x = 3; x + 1; }; y"), Ok(Type::Unit));
+    // Inner bindings go away with their block; the outer x shows again.
+    assert_eq!(value("let x = 1; let y = { let x = 10; x * 2 }; x + y"), Value::Int(21));
+}
+
+#[test]
+fn loop_returns_its_break_value() {
+    let source = "let mut counter = 0;
+        let result = loop {
+            counter += 1;
+            if counter == 10 { break counter * 2; }
+        };
+        result";
+    assert_eq!(value(source), Value::Int(20));
+    assert_eq!(value("let x = loop { break; }; x"), Value::Unit);
+    assert_eq!(value("'outer: loop { loop { break 'outer 7; } }"), Value::Int(7));
+}
+
+#[test]
+fn while_and_for_loops() {
+    let countdown =
+        "let mut number = 3; while number != 0 { print(number, \"!\"); number -= 1; } print(\"LIFTOFF!!!\");";
+    let outcome = run(countdown).unwrap();
+    assert_eq!(outcome.output, ["3!", "2!", "1!", "LIFTOFF!!!"]);
+    assert_eq!(outcome.value, Value::Unit);
+
+    let outcome = run("for number in (1..4).rev() { print(number); }").unwrap();
+    assert_eq!(outcome.output, ["3", "2", "1"]);
+    assert_eq!(value("let mut sum = 0; for i in 1..=100 { sum += i; } sum"), Value::Int(5050));
+    assert_eq!(value("let mut n = 0; for i in 0..10 { if i % 2 == 0 { continue; } n += 1; } n"), Value::Int(5));
+}
+
+#[test]
+fn labelled_break_and_continue_leave_the_right_loop() {
+    let labels = "let mut count = 0;
+        'counting_up: loop {
+            let mut remaining = 10;
+            loop {
+                if remaining == 9 { break; }
+                if count == 2 { break 'counting_up; }
+                remaining -= 1;
+            }
+            count += 1;
+        }
+        count";
+    assert_eq!(value(labels), Value::Int(2));
+    let rows =
+        "let mut seen = 0; 'rows: for r in 0..3 { for c in 0..3 { if c > r { continue 'rows; } seen += 1; } } seen";
+    assert_eq!(value(rows), Value::Int(6));
+}
+
+#[test]
+fn if_condition_must_be_bool() {
+    let diagnostic = check("let number = 3;\nif number {\n    print(\"number was three\");\n}").unwrap_err();
+    assert_eq!(diagnostic.code, Some("E0308"));
+    assert_eq!(diagnostic.labels[0].text, "expected `bool`, found `i32`");
+    assert_eq!(code("while 1 { }"), "E0308");
+    assert_eq!(value("let number = 3; if number != 0 { 1 } else { 0 }"), Value::Int(1));
+}
+
+#[test]
+fn if_arms_must_agree() {
+    let source = "let condition = true;\nlet number = if condition { 5 } else { \"six\" };";
+    let diagnostic = check(source).unwrap_err();
+    assert_eq!(diagnostic.code, Some("E0308"));
+    assert_eq!(diagnostic.message, "`if` and `else` have incompatible types");
+    let rendered = diagnostic.render(source);
+    assert!(rendered.contains("expected `i32`, found `&str`"), "{rendered}");
+    assert!(rendered.contains("expected because of this"), "{rendered}");
+
+    assert_eq!(code("if true { 1 } else if false { 2 } else { false }"), "E0308");
+    // Without an else, the missing arm is `()`.
+    assert_eq!(code("let x = if true { 1 }; x"), "E0317");
+    // A diverging arm fits any type.
+    assert_eq!(
+        value("let mut n = 0; loop { n += 1; let m = if n < 3 { continue } else { n }; break m; }"),
+        Value::Int(3)
+    );
+}
+
+#[test]
+fn break_values_and_loop_types() {
+    assert_eq!(code("let x = while true { break 5; }; x"), "E0571");
+    assert_eq!(code("for i in 0..3 { break i; }"), "E0571");
+    assert_eq!(code("loop { if true { break 1; } break \"one\"; }"), "E0308");
+    assert_eq!(code("break;"), "E0268");
+    assert_eq!(code("loop { continue 'nowhere; }"), "E0426");
+    // A loop with no break never ends, so it has type `!`.
+    assert_eq!(check("loop { }"), Ok(Type::Never));
+    assert_eq!(check("while false { }"), Ok(Type::Unit));
+}
+
+#[test]
+fn bindings_and_statements() {
+    assert_eq!(code("x + 1"), "E0425");
+    assert_eq!(code("let x = 5; x = 6;"), "E0384");
+    assert_eq!(code("let mut x = 5; x = true;"), "E0308");
+    assert_eq!(code("let x: bool = 5;"), "E0308");
+    assert_eq!(code("let x: u8 = 5;"), "E0412");
+    assert_eq!(code("if true { 1 } else { 2 } 3"), "E0308");
+    assert_eq!(code("{ 1 } - 1"), "E0308");
+    assert_eq!(code("if true { 1 } else { 2 } - 2"), "E0308");
+    assert_eq!(value("({ 1 }) - 1"), Value::Int(0));
+    assert_eq!(value("let x = { 1 } - 1; x"), Value::Int(0));
+    assert_eq!(value("{ print(1) } - 1"), Value::Int(-1));
+    assert_eq!(code("let x = 5"), "syntax");
+    assert_eq!(code("1 < 2 < 3"), "syntax");
+    assert_eq!(code("let x = 2147483648;"), "syntax");
+    assert_eq!(value("-2147483648"), Value::Int(i32::MIN));
+    assert_eq!(value("let s: &str = \"hi\"; s == \"hi\" && !false"), Value::Bool(true));
+}
+
+#[test]
+fn arithmetic_panics_like_a_debug_build() {
+    let overflow = run("let x = 2147483647; x + 1").unwrap_err();
+    assert!(
+        matches!(&overflow, Error::Panic { message, .. } if message == "attempt to add with overflow"),
+        "{overflow:?}"
+    );
+    let divide = run("let zero = 0; 1 / zero").unwrap_err();
+    assert!(matches!(&divide, Error::Panic { message, .. } if message == "attempt to divide by zero"), "{divide:?}");
+    assert_eq!(value("2 + 3 * 4 - 10 / 3 % 2"), Value::Int(13));
+}
+
+#[test]
+fn infinite_loops_run_out_of_fuel() {
+    let stopped = run("let mut n = 0; loop { n += 1; }").unwrap_err();
+    assert!(matches!(stopped, Error::OutOfFuel { .. }), "{stopped:?}");
+    assert!(stopped.render("let mut n = 0; loop { n += 1; }").contains(&format!("after {FUEL} loop iterations")));
+}
+
+#[test]
+fn report_shows_output_then_result_or_error() {
+    assert_eq!(report("print(\"hi\"); \"done\""), "hi\nresult: &str = \"done\"\n");
+    assert_eq!(
+        report("print(1); let x = 2147483647; x * 2"),
+        "1\nthread 'main' panicked at 1:31:\nattempt to multiply with overflow\n"
+    );
+    assert!(report("if 1 { }").starts_with("error[E0308]: mismatched types\n"));
+}
+
+#[test]
+fn deep_nesting_is_an_error_not_a_stack_overflow() {
+    let nested = |depth: usize| {
+        [
+            format!("{}1{}", "(".repeat(depth), ")".repeat(depth)),
+            format!("{}1{}", "{".repeat(depth), "}".repeat(depth)),
+            vec!["1"; depth + 1].join(" + "),
+            format!("{}true", "!".repeat(depth)),
+            // The blocks of the last `else if` are one level deeper than it.
+            format!("if false {{ 0 }}{} else {{ 1 }}", " else if false { 0 }".repeat(depth - 1)),
+        ]
+    };
+    // Debug builds need a lot of stack per level; this runs on a 2 MiB test thread.
+    for source in nested(MAX_NESTING) {
+        assert!(check(&source).is_ok(), "{source}");
+        run(&source).unwrap_or_else(|e| panic!("{source}: {}", e.render(&source)));
+    }
+    for depth in [MAX_NESTING + 1, 5000] {
+        for source in nested(depth) {
+            let diagnostic = check(&source).unwrap_err();
+            assert_eq!(diagnostic.message, "expression nested too deeply", "{source}");
+        }
+    }
+}
+
+#[test]
+fn unit_ranges_and_string_escapes() {
+    assert_eq!(value("()"), Value::Unit);
+    assert_eq!(check("let u: () = (); u"), Ok(Type::Unit));
+    assert_eq!(value("let x = { 1; }; x == ()"), Value::Bool(true));
+    assert_eq!(value("() != ()"), Value::Bool(false));
+    assert_eq!(value("(1..3) == (1..3)"), Value::Bool(true));
+    assert_eq!(value("(1..=3) != (1..=4)"), Value::Bool(true));
+    // Ranges have no ordering, and `.rev()` loses `==` too, as in std.
+    assert_eq!(code("(1..3) < (1..4)"), "E0369");
+    assert_eq!(code("(1..3).rev() == (1..3).rev()"), "E0369");
+
+    assert_eq!(value(r#""a\nb""#), Value::Str("a\nb".to_string()));
+    assert_eq!(value(r#""say \"hi\"\t\\""#), Value::Str("say \"hi\"\t\\".to_string()));
+    assert_eq!(report(r#"print("one\ttwo"); "\"""#), "one\ttwo\nresult: &str = \"\\\"\"\n");
+    let bad = check(r#""\q""#).unwrap_err();
+    assert_eq!(bad.message, "unknown character escape: `q`");
+    assert_eq!(check(r#""ends in \""#).unwrap_err().message, "unterminated double quote string");
+}
@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::Path;
+
+use lessons::lifetimes::{self, first_of, longest, no_dangle, ImportantExcerpt, Pair, CASES};
+
+#[test]
+fn no_dangle_hands_over_the_string() {
+    assert_eq!(no_dangle(), "hello");
+}
+
+#[test]
+fn longest_picks_the_longer_and_the_second_on_ties() {
+    assert_eq!(longest("abcd", "xyz"), "abcd");
+    assert_eq!(longest("ab", "xyz"), "xyz");
+    assert_eq!(longest("abc", "xyz"), "xyz");
+}
+
+#[test]
+fn first_of_may_outlive_its_second_argument() {
+    let string1 = String::from("kept");
+    let result;
+    {
+        let string2 = String::from("dropped");
+        result = first_of(&string1, &string2);
+    }
+    assert_eq!(result, "kept");
+}
+
+#[test]
+fn excerpt_borrows_from_the_text() {
+    let novel = String::from("Call me Ishmael. Some years ago...");
+    let i = ImportantExcerpt::first_sentence(&novel);
+    assert_eq!(i.part, "Call me Ishmael");
+    assert_eq!(i.part.as_ptr(), novel.as_ptr());
+    assert_eq!(i.announce_and_return_part("hi"), "Call me Ishmael");
+    assert_eq!(ImportantExcerpt::first_sentence("no dot").part, "no dot");
+
+    let right = String::from("b");
+    let pair = Pair { left: i.part, right: &right };
+    assert_eq!((pair.left, pair.right), ("Call me Ishmael", "b"));
+}
+
+// The table must agree with the compile_fail cases it points to.
+#[test]
+fn every_rejected_case_has_a_matching_compile_fail_file() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("compile_fail/references_borrowing");
+    for case in CASES {
+        match (case.code, case.compile_fail) {
+            (Some(code), Some(file)) => {
+                let source = fs::read_to_string(dir.join(file)).unwrap_or_else(|e| panic!("{file}: {e}"));
+                assert!(source.contains(&format!("//~ ERROR {code}")), "{file} isn't annotated with {code}");
+            }
+            (None, None) => {}
+            _ => panic!("{}: code and compile_fail file must come together", case.snippet),
+        }
+    }
+}
+
+#[test]
+fn explain_lists_every_case_with_its_rule() {
+    let text = lifetimes::explain();
+    for case in CASES {
+        assert!(text.contains(case.snippet));
+        assert!(text.contains(case.rule));
+    }
+    assert!(text.contains("error[E0106] (compile_fail/references_borrowing/dangle.rs)"));
+    assert_eq!(text.matches("  compiles\n").count(), 3);
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use lessons::loop_bench::{self, Config, VARIANTS};
+
+#[test]
+fn variants_agree() {
+    for size in [0, 1, 5, 63, 64, 65, 1000] {
+        let a = loop_bench::input(size);
+        let expected: u64 = a.iter().sum();
+        for variant in VARIANTS {
+            assert_eq!((variant.run)(&a), expected, "{} on {size} elements", variant.name);
+        }
+    }
+    let wrapping = [u64::MAX, 2, u64::MAX];
+    let sums: Vec<u64> = VARIANTS.iter().map(|v| (v.run)(&wrapping)).collect();
+    assert_eq!(sums, [0; 5]);
+}
+
+#[test]
+fn while_bound_stops_at_its_own_bound() {
+    let a = loop_bench::input(10);
+    assert_eq!(loop_bench::sum_while_bound(&a, 5), 10);
+    assert_eq!(loop_bench::sum_while_bound(&a, 0), 0);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn while_bound_past_the_end_panics() {
+    loop_bench::sum_while_bound(&[1, 2, 3], 5);
+}
+
+fn quick(sizes: Vec<usize>) -> Config {
+    Config { sizes, sample: Duration::from_micros(50), samples: 2 }
+}
+
+#[test]
+fn measures_every_variant_at_every_size() {
+    let report = loop_bench::run(&quick(vec![0, 5, 100]));
+    assert_eq!(report.measurements.len(), 3 * VARIANTS.len());
+    assert_eq!(report.optimized, !cfg!(debug_assertions));
+    for size in [0, 5, 100] {
+        for variant in VARIANTS {
+            let m = report.get(variant.name, size).unwrap();
+            assert!(m.iterations >= 1);
+            assert!(m.ns_per_element().is_finite());
+        }
+    }
+    assert!(report.get("while index", 7).is_none());
+}
+
+#[test]
+fn table_has_a_row_per_size() {
+    let report = loop_bench::run(&quick(vec![5, 64]));
+    let text = report.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 4, "{text}");
+    assert!(lines[0].starts_with("ns per element"));
+    let header: Vec<&str> = lines[1].split("  ").map(str::trim).filter(|s| !s.is_empty()).collect();
+    assert_eq!(
+        header,
+        [
+            "size",
+            "while index",
+            "while < bound",
+            "for element",
+            "iter fold",
+            "get_unchecked",
+            "while / for",
+            "bound / for"
+        ]
+    );
+    assert_eq!(lines[2].split_whitespace().next(), Some("5"));
+    assert_eq!(lines[3].split_whitespace().next(), Some("64"));
+    assert!(lines[3].ends_with('x') || lines[3].ends_with('-'), "{text}");
+}
@@ -0,0 +1,130 @@
+use lessons::loop_trace::{Event, Kind, LoopTrace};
+
+/// The lesson's 'counting_up loops, traced.
+fn counting_up() -> (LoopTrace, i32) {
+    let mut t = LoopTrace::new();
+    let mut count = 0;
+    t.enter(Kind::Loop, Some("'counting_up"));
+    'counting_up: loop {
+        t.iteration();
+        let mut remaining = 10;
+        t.enter(Kind::Loop, None);
+        loop {
+            t.iteration();
+            if remaining == 9 {
+                t.break_loop(None);
+                break;
+            }
+            if count == 2 {
+                t.break_loop(Some("'counting_up"));
+                break 'counting_up;
+            }
+            remaining -= 1;
+        }
+        count += 1;
+    }
+    (t, count)
+}
+
+#[test]
+fn labelled_break_leaves_both_loops() {
+    let (t, count) = counting_up();
+    assert_eq!(count, 2);
+    assert_eq!(t.depth(), 0);
+
+    let breaks: Vec<&Event> = t.events().iter().filter(|e| matches!(e, Event::Break { .. })).collect();
+    assert_eq!(breaks.len(), 3);
+    assert_eq!(breaks[0], &Event::Break { depth: 2, label: None, target_depth: 2, value: None });
+    assert_eq!(
+        breaks[2],
+        &Event::Break { depth: 2, label: Some("'counting_up".to_string()), target_depth: 1, value: None }
+    );
+
+    let tail = &t.events()[t.events().len() - 3..];
+    assert!(matches!(tail[0], Event::Break { .. }));
+    assert_eq!(tail[1], Event::Exit { depth: 2, kind: Kind::Loop, label: None, iterations: 1, value: None });
+    assert_eq!(
+        tail[2],
+        Event::Exit { depth: 1, kind: Kind::Loop, label: Some("'counting_up".to_string()), iterations: 3, value: None }
+    );
+}
+
+#[test]
+fn break_value_ends_the_loop_with_it() {
+    let mut t = LoopTrace::new();
+    let mut counter = 0;
+    t.enter(Kind::Loop, None);
+    let result = loop {
+        t.iteration();
+        counter += 1;
+        if counter == 20 {
+            t.break_value(None, &(counter * 2));
+            break counter * 2;
+        }
+    };
+    assert_eq!(result, 40);
+    assert_eq!(
+        t.events().last(),
+        Some(&Event::Exit { depth: 1, kind: Kind::Loop, label: None, iterations: 20, value: Some("40".to_string()) })
+    );
+    assert!(t.to_string().contains("} end of loop after 20 iterations = 40"));
+}
+
+#[test]
+fn continue_to_an_outer_label_ends_the_inner_loop_only() {
+    let mut t = LoopTrace::new();
+    t.enter(Kind::For, Some("'outer"));
+    for _ in 0..2 {
+        t.iteration();
+        t.enter(Kind::While, None);
+        t.iteration();
+        t.continue_loop(Some("'outer"));
+    }
+    t.exit();
+
+    assert_eq!(t.depth(), 0);
+    let exits: Vec<(usize, usize)> = t
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::Exit { depth, iterations, .. } => Some((*depth, *iterations)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(exits, [(2, 1), (2, 1), (1, 2)]);
+}
+
+#[test]
+fn timeline_is_indented_by_depth() {
+    let (t, _) = counting_up();
+    let text = t.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "1  'counting_up: loop {");
+    assert_eq!(lines[1], "1    iteration 1");
+    assert_eq!(lines[2], "2      loop {");
+    assert_eq!(lines[3], "2        iteration 1");
+    assert!(text.contains("2          break 'counting_up  -> leaves 2 loops, back to depth 0\n"), "{text}");
+    assert_eq!(lines.last(), Some(&"1  } end of 'counting_up: loop after 3 iterations"));
+}
+
+#[test]
+#[should_panic(expected = "E0571")]
+fn break_value_from_while_is_rejected() {
+    let mut t = LoopTrace::new();
+    t.enter(Kind::While, None);
+    t.break_value(None, &1);
+}
+
+#[test]
+#[should_panic(expected = "E0426")]
+fn undeclared_label_is_rejected() {
+    let mut t = LoopTrace::new();
+    t.enter(Kind::Loop, Some("'a"));
+    t.break_loop(Some("'b"));
+}
+
+#[test]
+#[should_panic(expected = "E0268")]
+fn break_outside_a_loop_is_rejected() {
+    LoopTrace::new().break_loop(None);
+}
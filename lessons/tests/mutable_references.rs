@@ -0,0 +1,32 @@
+use lessons::borrowing::{change, replace};
+
+#[test]
+fn change_appends_through_the_reference() {
+    let mut s = String::from("hello");
+    change(&mut s);
+    assert_eq!(s, "hello, world");
+}
+
+#[test]
+fn replace_overwrites_the_callers_string() {
+    let mut s = String::from("hello");
+    let r = &mut s;
+    replace(r, "goodbye");
+    replace(r, "again"); // r is reborrowed for each call, not moved
+    assert_eq!(s, "again");
+}
+
+#[test]
+fn borrows_in_turn_are_fine() {
+    let mut s = String::from("a");
+    {
+        let r1 = &mut s;
+        r1.push('b');
+    }
+    let r2 = &mut s;
+    r2.push('c');
+
+    let r3 = &s;
+    let r4 = &s;
+    assert_eq!((r3.as_str(), r4.as_str()), ("abc", "abc"));
+}
@@ -0,0 +1,154 @@
+use std::hint::black_box;
+use std::panic;
+
+use lessons::integers::overflow::{explore, Exploration, Op};
+use lessons::integers::IntType;
+
+fn run(op: &str, lhs: &str, rhs: &str, ty: &str) -> Exploration {
+    explore(op.parse().unwrap(), lhs, rhs, ty.parse().unwrap()).unwrap()
+}
+
+fn ok(value: &str) -> Result<String, String> {
+    Ok(value.to_string())
+}
+
+fn panics(message: &str) -> Result<String, String> {
+    Err(format!("panics: {message}"))
+}
+
+#[test]
+fn u8_addition_past_255() {
+    let e = run("+", "200", "100", "u8");
+    assert!(e.overflows);
+    assert_eq!(e.wrapping, ok("44"));
+    assert_eq!(e.checked, ok("None"));
+    assert_eq!(e.overflowing, ok("(44, true)"));
+    assert_eq!(e.saturating, ok("255"));
+    assert_eq!(e.debug, panics("attempt to add with overflow"));
+    assert_eq!(e.release, ok("44"));
+}
+
+#[test]
+fn in_range_results_agree_everywhere() {
+    let e = run("sub", "-5", "3", "i64");
+    assert!(!e.overflows);
+    assert_eq!(e.checked, ok("Some(-8)"));
+    assert_eq!(e.overflowing, ok("(-8, false)"));
+    for outcome in [&e.wrapping, &e.saturating, &e.debug, &e.release] {
+        assert_eq!(outcome, &ok("-8"));
+    }
+}
+
+#[test]
+fn signed_division_overflow_panics_even_in_release() {
+    let e = run("div", "-128", "-1", "i8");
+    assert_eq!(e.wrapping, ok("-128"));
+    assert_eq!(e.saturating, ok("127"));
+    assert_eq!(e.debug, panics("attempt to divide with overflow"));
+    assert_eq!(e.release, panics("attempt to divide with overflow"));
+}
+
+#[test]
+fn division_by_zero_only_has_checked() {
+    let e = run("rem", "7", "0", "u16");
+    assert_eq!(e.checked, ok("None"));
+    assert_eq!(e.wrapping, panics("attempt to calculate the remainder with a divisor of zero"));
+    assert_eq!(e.saturating, Err("std has no saturating_rem".to_string()));
+    assert_eq!(e.release, e.wrapping);
+}
+
+#[test]
+fn pow_takes_a_u32_exponent() {
+    let e = run("pow", "2", "64", "u64");
+    assert_eq!(e.wrapping, ok("0"));
+    assert_eq!(e.saturating, ok("18446744073709551615"));
+    assert_eq!(e.debug, panics("attempt to multiply with overflow"));
+    assert!(explore(Op::Pow, "2", "-1", IntType::U64).unwrap_err().contains("doesn't fit in u32"));
+}
+
+#[test]
+fn every_type_and_the_arch_dependent_ones() {
+    for ty in IntType::ALL {
+        let e = explore(Op::Add, "1", "1", ty).unwrap();
+        assert_eq!(e.wrapping, ok("2"), "{ty}");
+    }
+    let e = run("sub", "0", "1", "usize");
+    assert_eq!(e.wrapping, Ok(usize::MAX.to_string()));
+    let e = run("mul", &i128::MAX.to_string(), "2", "i128");
+    assert_eq!(e.wrapping, ok("-2"));
+}
+
+#[test]
+fn bad_input_is_reported() {
+    assert_eq!(explore(Op::Add, "256", "1", IntType::U8).unwrap_err(), "`256` doesn't fit in u8 (0..=255)");
+    assert_eq!(explore(Op::Add, "-1", "1", IntType::U8).unwrap_err(), "`-1` doesn't fit in u8 (0..=255)");
+    assert_eq!(explore(Op::Add, "x", "1", IntType::U8).unwrap_err(), "`x` is not an integer");
+    assert!("shl".parse::<Op>().is_err());
+    assert!("u7".parse::<IntType>().is_err());
+    assert_eq!(run("add", "1_000", "1", "i32").wrapping, ok("1001"));
+}
+
+/// The panic message of `f`, if it panics.
+fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> Option<String> {
+    let payload = panic::catch_unwind(f).err()?;
+    payload.downcast_ref::<&str>().map(|s| s.to_string()).or_else(|| payload.downcast_ref::<String>().cloned())
+}
+
+// Debug builds have overflow checks on, so the "debug" column must match what
+// the plain operators really do here.
+#[test]
+#[cfg(debug_assertions)]
+fn debug_column_matches_the_real_operators() {
+    panic::set_hook(Box::new(|_| {}));
+    let real = [
+        (
+            Op::Add,
+            panic_message(|| {
+                black_box(black_box(200u8) + black_box(100u8));
+            }),
+        ),
+        (
+            Op::Sub,
+            panic_message(|| {
+                black_box(black_box(0u32) - black_box(1u32));
+            }),
+        ),
+        (
+            Op::Mul,
+            panic_message(|| {
+                black_box(black_box(100i8) * black_box(2i8));
+            }),
+        ),
+        (
+            Op::Div,
+            panic_message(|| {
+                black_box(black_box(-128i8) / black_box(-1i8));
+            }),
+        ),
+        (
+            Op::Rem,
+            panic_message(|| {
+                black_box(black_box(7u16) % black_box(0u16));
+            }),
+        ),
+        (
+            Op::Pow,
+            panic_message(|| {
+                black_box(black_box(2u64).pow(black_box(64)));
+            }),
+        ),
+    ];
+    let _ = panic::take_hook();
+    let predicted = [
+        explore(Op::Add, "200", "100", IntType::U8),
+        explore(Op::Sub, "0", "1", IntType::U32),
+        explore(Op::Mul, "100", "2", IntType::I8),
+        explore(Op::Div, "-128", "-1", IntType::I8),
+        explore(Op::Rem, "7", "0", IntType::U16),
+        explore(Op::Pow, "2", "64", IntType::U64),
+    ];
+    for ((op, real), predicted) in real.into_iter().zip(predicted) {
+        let predicted = predicted.unwrap().debug.unwrap_err();
+        assert_eq!(Some(predicted.trim_start_matches("panics: ").to_string()), real, "{op}");
+    }
+}
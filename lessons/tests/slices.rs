@@ -0,0 +1,61 @@
+use lessons::slices::{byte_range, first_word, first_word_end, nth_word, words};
+
+#[test]
+fn first_word_of_strings_and_literals() {
+    let s = String::from("hello world");
+    assert_eq!(first_word(&s), "hello");
+    assert_eq!(first_word(&s[6..]), "world");
+    assert_eq!(first_word("hello"), "hello");
+    assert_eq!(first_word(""), "");
+    assert_eq!(first_word(" leading"), "");
+}
+
+#[test]
+fn nth_word_skips_runs_of_whitespace() {
+    let s = "  the quick\tbrown\n\nfox ";
+    assert_eq!(nth_word(s, 0), Some("the"));
+    assert_eq!(nth_word(s, 3), Some("fox"));
+    assert_eq!(nth_word(s, 4), None);
+    assert_eq!(words(s).collect::<Vec<_>>(), ["the", "quick", "brown", "fox"]);
+    assert_eq!(words("   ").count(), 0);
+}
+
+#[test]
+fn cuts_fall_on_char_boundaries() {
+    let s = String::from("ℤ😻 are\u{3000}cats");
+    assert_eq!(first_word_end(&s), 7, "ℤ is 3 bytes and 😻 is 4");
+    assert_eq!(first_word(&s), "ℤ😻");
+    assert_eq!(words(&s).collect::<Vec<_>>(), ["ℤ😻", "are", "cats"]);
+    assert_eq!(nth_word("😻", 0), Some("😻"));
+    assert_eq!(s.get(..1), None, "byte 1 is inside 'ℤ'");
+}
+
+#[test]
+fn words_are_views_into_the_original_buffer() {
+    let s = String::from("hello world");
+    let word = nth_word(&s, 1).unwrap();
+    assert_eq!(word.as_ptr(), s[6..].as_ptr(), "no copy was made");
+    assert_eq!(byte_range(&s, word), Some(6..11));
+    assert_eq!(byte_range(&s, "elsewhere"), None);
+}
+
+#[test]
+fn a_slice_keeps_the_string_borrowed_until_its_last_use() {
+    let mut s = String::from("hello world");
+    let word = first_word(&s);
+    // s.clear() here would not compile (compile_fail/slices/clear_while_word_borrowed.rs):
+    // the borrow taken by first_word lasts as long as `word` is used.
+    assert_eq!(word, "hello");
+    // word's last use was above, so the borrow has ended and s can change.
+    s.clear();
+    assert_eq!(s, "");
+}
+
+#[test]
+fn an_index_does_not_keep_anything_borrowed() {
+    let mut s = String::from("hello world");
+    let end = first_word_end(&s);
+    s.clear();
+    assert_eq!(end, 5, "the index is stale");
+    assert_eq!(s.get(..end), None);
+}
@@ -0,0 +1,70 @@
+use lessons::traced::{take_events, Event, TracedString};
+
+fn takes_ownership(some_string: TracedString) -> usize {
+    some_string.len()
+}
+
+#[test]
+fn new_records_an_allocation_at_the_call_site() {
+    take_events();
+    let line = line!() + 1;
+    let s = TracedString::new("s", "hello");
+
+    let events = take_events();
+    assert_eq!(events.len(), 1);
+    let Event::Alloc { name, value, capacity, at } = &events[0] else {
+        panic!("expected an alloc, got {events:?}");
+    };
+    assert_eq!((name.as_str(), value.as_str()), ("s", "hello"));
+    assert!(*capacity >= 5);
+    assert_eq!(at.line(), line);
+    assert!(at.file().ends_with("traced.rs"));
+    drop(s);
+}
+
+#[test]
+fn moving_into_a_function_drops_only_once() {
+    take_events();
+    let s = TracedString::new("s", "hello");
+    assert_eq!(takes_ownership(s.moved("some_string")), 5);
+
+    let events = take_events();
+    assert!(matches!(&events[1], Event::Move { from, to, .. } if from == "s" && to == "some_string"));
+    let drops: Vec<&Event> = events.iter().filter(|e| matches!(e, Event::Drop { .. })).collect();
+    assert_eq!(drops.len(), 1);
+    assert!(matches!(drops[0], Event::Drop { name, .. } if name == "some_string"));
+}
+
+#[test]
+fn clone_is_a_separate_owner_that_is_dropped_separately() {
+    take_events();
+    {
+        let s1 = TracedString::new("s1", "hello");
+        let s2 = s1.clone();
+        let s3 = s1.clone_as("s3");
+        assert_eq!(s2.as_str(), s1.as_str());
+        assert_eq!(s2.name(), "s1.clone()");
+        assert_eq!(s3.name(), "s3");
+    }
+
+    let kinds: Vec<String> = take_events()
+        .iter()
+        .map(|e| match e {
+            Event::Alloc { name, .. } => format!("alloc {name}"),
+            Event::Move { to, .. } => format!("move {to}"),
+            Event::Clone { to, .. } => format!("clone {to}"),
+            Event::Drop { name, .. } => format!("drop {name}"),
+        })
+        .collect();
+    assert_eq!(kinds, ["alloc s1", "clone s1.clone()", "clone s3", "drop s3", "drop s1.clone()", "drop s1"]);
+}
+
+#[test]
+fn display_shows_the_file_and_line() {
+    take_events();
+    let s = TracedString::new("s", "hi");
+    let shown = take_events()[0].to_string();
+    assert!(shown.starts_with("alloc  s = \"hi\""), "{shown}");
+    assert!(shown.contains(" at traced.rs:"), "{shown}");
+    drop(s);
+}